@@ -0,0 +1,72 @@
+//! Decoding of the events the intent program emits through self-CPI, which are recorded in
+//! the inner instructions of a transaction rather than in its logs
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey};
+
+use crate::events::{decode_event, IntentEvent};
+
+/// Decodes the event of an inner instruction, returning `None` for instructions which are not
+/// event instructions of the intent program
+pub fn decode_instruction(program_id: &Pubkey, data: &[u8]) -> Option<IntentEvent> {
+    if *program_id != intent::ID {
+        return None;
+    }
+
+    data.strip_prefix(&EVENT_IX_TAG_LE).and_then(decode_event)
+}
+
+/// Returns the events of the inner instructions of a transaction, given as the program id and
+/// the data of each instruction in execution order
+pub fn decode_inner_instructions<'a, I>(instructions: I) -> Vec<IntentEvent>
+where
+    I: IntoIterator<Item = (&'a Pubkey, &'a [u8])>,
+{
+    instructions
+        .into_iter()
+        .filter_map(|(program_id, data)| decode_instruction(program_id, data))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Event;
+    use intent::event::{OrderClosed, SendMessage};
+
+    use super::*;
+
+    fn event_data(event: impl Event) -> Vec<u8> {
+        EVENT_IX_TAG_LE.into_iter().chain(event.data()).collect()
+    }
+
+    #[test]
+    fn test_decode_inner_instructions() {
+        let other_program = Pubkey::new_unique();
+        let send_message = event_data(SendMessage {
+            targetNetwork: "icon".to_string(),
+            sn: 7,
+            msg: vec![1, 2, 3],
+        });
+        let order_closed = event_data(OrderClosed { id: 3 });
+
+        let events = decode_inner_instructions([
+            (&intent::ID, &send_message[..]),
+            (&other_program, &order_closed[..]),
+            (&intent::ID, &[1, 2, 3][..]),
+            (&intent::ID, &order_closed[..]),
+        ]);
+
+        assert!(matches!(
+            &events[..],
+            [IntentEvent::SendMessage(message), IntentEvent::OrderClosed(closed)]
+                if message.targetNetwork == "icon" && message.sn == 7 && message.msg == [1, 2, 3]
+                    && closed.id == 3
+        ));
+    }
+
+    #[test]
+    fn test_decode_instruction_requires_event_tag() {
+        let order_closed = OrderClosed { id: 3 }.data();
+
+        assert!(decode_instruction(&intent::ID, &order_closed).is_none());
+    }
+}
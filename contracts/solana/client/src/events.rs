@@ -0,0 +1,85 @@
+//! Decoding of the events emitted by the intent program in transaction logs. Order and
+//! message events are emitted through self-CPI instead, see `decoder`.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use intent::event::*;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+macro_rules! intent_events {
+    ($($name:ident),* $(,)?) => {
+        /// An event of the intent program
+        pub enum IntentEvent {
+            $($name($name),)*
+        }
+
+        /// Decodes an event from its discriminator prefixed Borsh data, returning `None` for
+        /// data which is not an event of the intent program
+        pub fn decode_event(data: &[u8]) -> Option<IntentEvent> {
+            if data.len() < 8 {
+                return None;
+            }
+            let (discriminator, mut event) = data.split_at(8);
+
+            $(
+                if discriminator == $name::DISCRIMINATOR {
+                    return $name::deserialize(&mut event).ok().map(IntentEvent::$name);
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+intent_events!(
+    SwapIntent,
+    OrderFilled,
+    OrderCancelled,
+    OrderClosed,
+    AdminProposed,
+    AdminChanged,
+    RelayerChanged,
+    FeeHandlerChanged,
+    GuardianChanged,
+    ConfigMigrated,
+    Paused,
+    Unpaused,
+    SendMessage,
+);
+
+/// Returns the events emitted by the intent program in the logs of a transaction. Logs of
+/// other programs, including programs invoked by the intent program, are skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<IntentEvent> {
+    let program_id = intent::ID.to_string();
+    let mut invocations: Vec<bool> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if invocations.last() == Some(&true) {
+                if let Some(event) = STANDARD
+                    .decode(data)
+                    .ok()
+                    .and_then(|data| decode_event(&data))
+                {
+                    events.push(event);
+                }
+            }
+        } else if let Some(invoke) = log.strip_prefix("Program ") {
+            let mut parts = invoke.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program == program_id),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
@@ -0,0 +1,590 @@
+//! Typed builders of the intent program instructions. Optional accounts the program does
+//! not need for an order are left out, which anchor encodes as the program id.
+
+use std::str::FromStr;
+
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use intent::{
+    accounts,
+    constants::{NATIVE_ADDRESS, NATIVE_MINT},
+    error::IntentError,
+    instruction,
+    types::{
+        order_cancel::Cancel,
+        order_fill::OrderFill,
+        order_message::{MessageType, OrderMessage},
+        swap_order::SwapOrder,
+    },
+};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: intent::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn key(address: &str) -> Pubkey {
+    Pubkey::from_str(address).expect("invalid Solana address")
+}
+
+fn none() -> AccountMeta {
+    AccountMeta::new_readonly(intent::ID, false)
+}
+
+pub fn initialize(signer: Pubkey, network_id: String, fee_handler: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeCtx {
+            config: pda::config().0,
+            native_vault_account: pda::vault_native().0,
+            signer,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {
+            network_id,
+            fee_handler,
+        },
+    )
+}
+
+pub fn migrate_config(admin: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateConfigCtx {
+            config: pda::config().0,
+            admin,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateConfig {},
+    )
+}
+
+pub fn propose_admin(admin: Pubkey, account: Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAdminCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::ProposeAdmin { account },
+    )
+}
+
+pub fn accept_admin(pending_admin: Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdminCtx {
+            config: pda::config().0,
+            pending_admin,
+        },
+        instruction::AcceptAdmin {},
+    )
+}
+
+pub fn set_relayer(admin: Pubkey, relayer: Pubkey) -> Instruction {
+    build(
+        accounts::SetRelayerCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::SetRelayer { relayer },
+    )
+}
+
+pub fn set_protocol_fee(admin: Pubkey, fee: u64) -> Instruction {
+    build(
+        accounts::SetFeeCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::SetProtocolFee { fee },
+    )
+}
+
+pub fn set_fee_handler(admin: Pubkey, fee_handler: Pubkey) -> Instruction {
+    build(
+        accounts::SetFeeHandlerCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::SetFeeHandler { fee_handler },
+    )
+}
+
+pub fn set_guardian(admin: Pubkey, guardian: Pubkey) -> Instruction {
+    build(
+        accounts::SetGuardianCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::SetGuardian { guardian },
+    )
+}
+
+/// Pauses the instructions of `flags`, signed by the admin or the guardian
+pub fn pause(authority: Pubkey, flags: u8) -> Instruction {
+    build(
+        accounts::PauseCtx {
+            config: pda::config().0,
+            authority,
+        },
+        instruction::Pause { flags },
+    )
+}
+
+pub fn unpause(admin: Pubkey, flags: u8) -> Instruction {
+    build(
+        accounts::UnpauseCtx {
+            config: pda::config().0,
+            admin,
+        },
+        instruction::Unpause { flags },
+    )
+}
+
+pub fn set_verifier_set(admin: Pubkey, verifiers: Vec<Pubkey>, threshold: u8) -> Instruction {
+    build(
+        accounts::SetVerifierSetCtx {
+            config: pda::config().0,
+            verifier_set: pda::verifier_set().0,
+            admin,
+            system_program: system_program::ID,
+        },
+        instruction::SetVerifierSet {
+            verifiers,
+            threshold,
+        },
+    )
+}
+
+/// Claims the fees accrued in the fee vault of `mint`, the system program id for native SOL.
+/// `token_program` owns `mint` and is ignored for native SOL.
+pub fn claim_fees(fee_handler: Pubkey, mint: Pubkey, token_program: Pubkey) -> Instruction {
+    let is_native = mint == system_program::ID;
+
+    build(
+        accounts::ClaimFeesCtx {
+            fee_handler,
+            system_program: system_program::ID,
+            config: pda::config().0,
+            fee_vault: pda::fee_vault(&mint).0,
+            fee_handler_token_account: (!is_native).then(|| {
+                get_associated_token_address_with_program_id(&fee_handler, &mint, &token_program)
+            }),
+            token_mint: (!is_native).then_some(mint),
+            token_program: if is_native {
+                anchor_spl::token::ID
+            } else {
+                token_program
+            },
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimFees { mint },
+    )
+}
+
+/// Creates `order`, signed by its creator. `deposit_id` is the id the order is assigned,
+/// the `deposit_id` of the config plus one, and `token_program` owns the mint of `token`.
+pub fn swap(order: &SwapOrder, deposit_id: u128, token_program: Pubkey) -> Instruction {
+    let signer = key(&order.creator());
+    let is_native = order.token() == NATIVE_ADDRESS;
+    let mint = key(&order.token());
+
+    build(
+        accounts::SwapCtx {
+            signer,
+            system_program: system_program::ID,
+            config: pda::config().0,
+            order_account: pda::order(deposit_id).0,
+            order_index: pda::order_index(&signer).0,
+            native_vault_account: is_native.then(|| pda::vault_native().0),
+            token_vault_account: (!is_native).then(|| pda::vault_token(&mint).0),
+            signer_token_account: (!is_native).then(|| {
+                get_associated_token_address_with_program_id(&signer, &mint, &token_program)
+            }),
+            mint: (!is_native).then_some(mint),
+            token_program: if is_native {
+                anchor_spl::token::ID
+            } else {
+                token_program
+            },
+            event_authority: pda::event_authority().0,
+            program: intent::ID,
+        },
+        instruction::Swap {
+            order: order.clone(),
+        },
+    )
+}
+
+/// Fills `order` on its destination chain, signed by `solver` and paid back to
+/// `solver_address` on the source chain. `token_program` owns the SPL mints of the order,
+/// wrapped SOL is always owned by the token program.
+///
+/// The accounts of the order hook are appended, and for same chain orders the accounts of
+/// `resolve_fill` which resolves the order in the same transaction.
+pub fn fill(
+    solver: Pubkey,
+    order: &SwapOrder,
+    solver_address: String,
+    token_program: Pubkey,
+) -> Instruction {
+    let destination_address = key(&order.dst_address());
+    let to_token = key(&order.to_token());
+    let fee_vault = pda::fee_vault(&to_token).0;
+    let native_mint = key(NATIVE_MINT);
+    let payout_token = order.payout_to_token();
+
+    let mut fill_accounts = accounts::FillCtx {
+        signer: solver,
+        system_program: system_program::ID,
+        config: pda::config().0,
+        destination_address,
+        order_finished: pda::order_finished(order).0,
+        fee_vault_native: None,
+        fee_vault_token_account: None,
+        destination_token_account: None,
+        signer_token_account: None,
+        mint: None,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: associated_token::ID,
+        event_authority: pda::event_authority().0,
+        program: intent::ID,
+    };
+
+    if order.to_token() == NATIVE_ADDRESS {
+        fill_accounts.fee_vault_native = Some(fee_vault);
+
+        // Native SOL delivered as wrapped SOL
+        if payout_token == NATIVE_MINT {
+            fill_accounts.destination_token_account =
+                Some(get_associated_token_address_with_program_id(
+                    &destination_address,
+                    &native_mint,
+                    &anchor_spl::token::ID,
+                ));
+            fill_accounts.mint = Some(native_mint);
+        }
+    } else if payout_token == NATIVE_ADDRESS {
+        // Wrapped SOL delivered as native SOL
+        fill_accounts.fee_vault_token_account = Some(fee_vault);
+        fill_accounts.mint = Some(to_token);
+    } else {
+        fill_accounts.fee_vault_token_account = Some(fee_vault);
+        fill_accounts.destination_token_account =
+            Some(get_associated_token_address_with_program_id(
+                &destination_address,
+                &to_token,
+                &token_program,
+            ));
+        fill_accounts.signer_token_account = Some(get_associated_token_address_with_program_id(
+            &solver,
+            &to_token,
+            &token_program,
+        ));
+        fill_accounts.mint = Some(to_token);
+        fill_accounts.token_program = token_program;
+    }
+
+    let mut ix = build(
+        fill_accounts,
+        instruction::Fill {
+            order: order.clone(),
+            solver_address,
+        },
+    );
+
+    // The resolve accounts come first as `invoke_resolve` passes the remaining accounts in
+    // order, while the hook accounts are looked up by key
+    if order.src_nid() == order.dst_nid() {
+        ix.accounts
+            .extend(resolve_fill_accounts(order, solver, token_program));
+    }
+
+    if let Some(hook) = order
+        .order_data()
+        .ok()
+        .as_ref()
+        .and_then(|data| data.hook())
+    {
+        ix.accounts
+            .push(AccountMeta::new_readonly(hook.program_id(), false));
+        for account in hook.accounts() {
+            if account.is_writable {
+                ix.accounts.push(AccountMeta::new(account.pubkey, false));
+            } else {
+                ix.accounts
+                    .push(AccountMeta::new_readonly(account.pubkey, false));
+            }
+        }
+    }
+
+    ix
+}
+
+/// Fills cross-chain `orders` of the same `to_token` at once, see `fill`
+pub fn fill_batch(
+    solver: Pubkey,
+    to_token: Pubkey,
+    orders: &[SwapOrder],
+    solver_address: String,
+    token_program: Pubkey,
+) -> Instruction {
+    let is_native = to_token == system_program::ID;
+    let fee_vault = pda::fee_vault(&to_token).0;
+
+    let mut ix = build(
+        accounts::FillBatchCtx {
+            signer: solver,
+            system_program: system_program::ID,
+            config: pda::config().0,
+            fee_vault_native: is_native.then_some(fee_vault),
+            fee_vault_token_account: (!is_native).then_some(fee_vault),
+            signer_token_account: (!is_native).then(|| {
+                get_associated_token_address_with_program_id(&solver, &to_token, &token_program)
+            }),
+            mint: (!is_native).then_some(to_token),
+            token_program: if is_native {
+                anchor_spl::token::ID
+            } else {
+                token_program
+            },
+            associated_token_program: associated_token::ID,
+            event_authority: pda::event_authority().0,
+            program: intent::ID,
+        },
+        instruction::FillBatch {
+            to_token,
+            orders: orders.to_vec(),
+            solver_address,
+        },
+    );
+
+    // Destination, order finished and destination token accounts of each order
+    for order in orders {
+        let destination_address = key(&order.dst_address());
+        let destination_token_account = if is_native {
+            intent::ID
+        } else {
+            get_associated_token_address_with_program_id(
+                &destination_address,
+                &to_token,
+                &token_program,
+            )
+        };
+
+        ix.accounts.extend([
+            AccountMeta::new(destination_address, false),
+            AccountMeta::new(pda::order_finished(order).0, false),
+            AccountMeta::new(destination_token_account, false),
+        ]);
+    }
+
+    ix
+}
+
+/// Cancels `order`, signed by its creator
+pub fn cancel(order: &SwapOrder) -> Instruction {
+    build(
+        accounts::CancelCtx {
+            signer: key(&order.creator()),
+            system_program: system_program::ID,
+            config: pda::config().0,
+            order_account: pda::order(order.id()).0,
+            order_finished: (order.src_nid() == order.dst_nid())
+                .then(|| pda::order_finished(order).0),
+            event_authority: pda::event_authority().0,
+            program: intent::ID,
+        },
+        instruction::Cancel {
+            order: order.clone(),
+        },
+    )
+}
+
+/// Cancels `order` once its deadline has passed, signed by any account
+pub fn expire(signer: Pubkey, order: &SwapOrder) -> Instruction {
+    build(
+        accounts::ExpireCtx {
+            signer,
+            system_program: system_program::ID,
+            config: pda::config().0,
+            order_account: pda::order(order.id()).0,
+            cancel_request: pda::cancel_request(order.id()).0,
+            order_finished: (order.src_nid() == order.dst_nid())
+                .then(|| pda::order_finished(order).0),
+            event_authority: pda::event_authority().0,
+            program: intent::ID,
+        },
+        instruction::Expire {
+            order: order.clone(),
+        },
+    )
+}
+
+/// Returns the rent of the cancel request of a settled order to `payer`, the account that
+/// expired it
+pub fn close_cancel_request(payer: Pubkey, order_id: u128) -> Instruction {
+    build(
+        accounts::CloseCancelRequestCtx {
+            order_account: pda::order(order_id).0,
+            cancel_request: pda::cancel_request(order_id).0,
+            payer,
+        },
+        instruction::CloseCancelRequest { order_id },
+    )
+}
+
+/// Delivers the message `msg` of `src_network`, signed by the relayer. The accounts resolving
+/// the fills or the cancel of the message are appended, `token_program` owns the SPL mints
+/// of the filled orders.
+pub fn recv_message(
+    relayer: Pubkey,
+    src_network: String,
+    conn_sn: u128,
+    msg: Vec<u8>,
+    token_program: Pubkey,
+) -> Result<Instruction, IntentError> {
+    let order_msg = OrderMessage::try_from(&msg)?;
+
+    let mut remaining_accounts = vec![];
+    match order_msg.message_type() {
+        MessageType::FILL => {
+            let fill = OrderFill::try_from(&order_msg.message())?;
+            let order = SwapOrder::try_from(&fill.order_bytes())?;
+            remaining_accounts.extend(resolve_fill_accounts(
+                &order,
+                key(&fill.solver()),
+                token_program,
+            ));
+        }
+        MessageType::CANCEL => {
+            let cancel = Cancel::try_from(&order_msg.message())?;
+            let order = SwapOrder::try_from(&cancel.order_bytes())?;
+            remaining_accounts.extend([
+                AccountMeta::new(pda::config().0, false),
+                AccountMeta::new(pda::order_finished(&order).0, false),
+            ]);
+            remaining_accounts.extend(event_cpi_accounts());
+        }
+    }
+
+    let mut ix = build(
+        accounts::RecvMessageCtx {
+            signer: relayer,
+            system_program: system_program::ID,
+            config: pda::config().0,
+            receipt_page: pda::receipt_page(&src_network, conn_sn).0,
+            legacy_receipt: pda::receipt(&src_network, conn_sn).0,
+            verifier_set: pda::verifier_set().0,
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        instruction::RecvMessage {
+            src_network,
+            conn_sn,
+            msg,
+        },
+    );
+    ix.accounts.extend(remaining_accounts);
+
+    Ok(ix)
+}
+
+/// Returns the accounts of `resolve_fill` following the accounts passed by `invoke_resolve`
+pub fn resolve_fill_accounts(
+    order: &SwapOrder,
+    solver: Pubkey,
+    token_program: Pubkey,
+) -> Vec<AccountMeta> {
+    let order_creator = key(&order.creator());
+    let payout_token = order.payout_token();
+    let native_mint = key(NATIVE_MINT);
+
+    let mut account_metas = vec![
+        AccountMeta::new(pda::config().0, false),
+        AccountMeta::new(pda::order(order.id()).0, false),
+        AccountMeta::new(order_creator, false),
+        AccountMeta::new(pda::order_index(&order_creator).0, false),
+        AccountMeta::new(solver, false),
+    ];
+
+    let token_program = if order.token() == NATIVE_ADDRESS && payout_token == NATIVE_MINT {
+        // Escrowed native SOL paid out as wrapped SOL
+        account_metas.extend([
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &solver,
+                    &native_mint,
+                    &anchor_spl::token::ID,
+                ),
+                false,
+            ),
+            AccountMeta::new(pda::vault_native().0, false),
+            none(),
+            AccountMeta::new_readonly(native_mint, false),
+        ]);
+        anchor_spl::token::ID
+    } else if order.token() == NATIVE_ADDRESS {
+        account_metas.extend([
+            none(),
+            AccountMeta::new(pda::vault_native().0, false),
+            none(),
+            none(),
+        ]);
+        anchor_spl::token::ID
+    } else {
+        let mint = key(&order.token());
+        let token_program = if order.token() == NATIVE_MINT {
+            anchor_spl::token::ID
+        } else {
+            token_program
+        };
+
+        // The solver token account is left out when wrapped SOL is paid out natively
+        let solver_token_account = if payout_token == NATIVE_ADDRESS {
+            none()
+        } else {
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&solver, &mint, &token_program),
+                false,
+            )
+        };
+
+        account_metas.extend([
+            solver_token_account,
+            none(),
+            AccountMeta::new(pda::vault_token(&mint).0, false),
+            AccountMeta::new_readonly(mint, false),
+        ]);
+        token_program
+    };
+
+    account_metas.extend([
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new_readonly(associated_token::ID, false),
+    ]);
+
+    // Temporary account unwrapping escrowed wrapped SOL
+    if order.token() == NATIVE_MINT && payout_token == NATIVE_ADDRESS {
+        account_metas.push(AccountMeta::new(pda::unwrap().0, false));
+    } else {
+        account_metas.push(none());
+    }
+
+    account_metas.extend(event_cpi_accounts());
+
+    account_metas
+}
+
+/// Returns the event authority and the program account of the `#[event_cpi]` contexts
+fn event_cpi_accounts() -> [AccountMeta; 2] {
+    [
+        AccountMeta::new_readonly(pda::event_authority().0, false),
+        AccountMeta::new_readonly(intent::ID, false),
+    ]
+}
@@ -0,0 +1,9 @@
+//! Client of the intent program: typed instruction builders, program derived addresses and
+//! decoding of the program events from logs and inner instructions
+
+pub mod decoder;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+pub use intent::{constants, error::IntentError, event, types, ID};
@@ -0,0 +1,85 @@
+//! Program derived addresses of the intent program, mirroring the seeds of its accounts
+
+use anchor_lang::prelude::Pubkey;
+use intent::{
+    constants::{FEE_VAULT_SEED_PREFIX, UNWRAP_SEED_PREFIX, VAULT_TOKEN_SEED_PREFIX},
+    state::{
+        CancelRequest, Config, OrderAccount, OrderIndex, Receipt, ReceiptPage, VaultNative,
+        VerifierSet,
+    },
+    types::swap_order::SwapOrder,
+};
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &intent::ID)
+}
+
+pub fn config() -> (Pubkey, u8) {
+    find(&[Config::SEED_PREFIX.as_bytes()])
+}
+
+/// Vault escrowing the native SOL of open orders
+pub fn vault_native() -> (Pubkey, u8) {
+    find(&[VaultNative::SEED_PREFIX.as_bytes()])
+}
+
+/// Vault escrowing the `mint` tokens of open orders
+pub fn vault_token(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[VAULT_TOKEN_SEED_PREFIX.as_bytes(), mint.as_ref()])
+}
+
+/// Vault accruing the protocol fees of `mint`, the system program id for native SOL
+pub fn fee_vault(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[FEE_VAULT_SEED_PREFIX.as_bytes(), mint.as_ref()])
+}
+
+/// Temporary account unwrapping escrowed wrapped SOL
+pub fn unwrap() -> (Pubkey, u8) {
+    find(&[UNWRAP_SEED_PREFIX.as_bytes()])
+}
+
+pub fn order(id: u128) -> (Pubkey, u8) {
+    find(&[OrderAccount::SEED_PREFIX.as_bytes(), &id.to_be_bytes()])
+}
+
+/// Index of the open orders of `creator`
+pub fn order_index(creator: &Pubkey) -> (Pubkey, u8) {
+    find(&[OrderIndex::SEED_PREFIX.as_bytes(), creator.as_ref()])
+}
+
+/// Marker of the cancel sent by `expire` for the order `id`
+pub fn cancel_request(id: u128) -> (Pubkey, u8) {
+    find(&[CancelRequest::SEED_PREFIX.as_bytes(), &id.to_be_bytes()])
+}
+
+/// Replay protection of a filled or cancelled order, seeded by the order hash
+pub fn order_finished(order: &SwapOrder) -> (Pubkey, u8) {
+    find(&[&order.get_hash()])
+}
+
+/// Receipt page covering the connection sequence number `conn_sn` of `src_network`
+pub fn receipt_page(src_network: &str, conn_sn: u128) -> (Pubkey, u8) {
+    find(&[
+        ReceiptPage::SEED_PREFIX.as_bytes(),
+        src_network.as_bytes(),
+        &ReceiptPage::index(conn_sn).to_be_bytes(),
+    ])
+}
+
+/// Legacy per message receipt, see `migrate_receipt`
+pub fn receipt(src_network: &str, conn_sn: u128) -> (Pubkey, u8) {
+    find(&[
+        Receipt::SEED_PREFIX.as_bytes(),
+        src_network.as_bytes(),
+        &conn_sn.to_be_bytes(),
+    ])
+}
+
+pub fn verifier_set() -> (Pubkey, u8) {
+    find(&[VerifierSet::SEED_PREFIX.as_bytes()])
+}
+
+/// Authority signing the self-CPI through which the program emits its events
+pub fn event_authority() -> (Pubkey, u8) {
+    find(&[b"__event_authority"])
+}
@@ -0,0 +1,353 @@
+//! Runs the instruction builders against the program loaded in an in-process SVM. The
+//! tests need the program binary, so they are ignored by default and run after building
+//! it with `anchor build`:
+//!
+//! ```sh
+//! anchor build && cargo test -p intent-client -- --include-ignored
+//! ```
+
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use intent::{
+    state::{Config, Receipt},
+    types::{
+        order_data::OrderData,
+        order_fill::OrderFill,
+        order_message::{MessageType, OrderMessage},
+        swap_order::SwapOrder,
+    },
+};
+use intent_client::{
+    decoder::decode_inner_instructions,
+    events::{parse_logs, IntentEvent},
+    instructions, pda,
+};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+const PROGRAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy/intent.so");
+
+const SRC_NID: &str = "solana";
+const DST_NID: &str = "icon";
+
+struct TestContext {
+    svm: LiteSVM,
+    admin: Keypair,
+}
+
+impl TestContext {
+    fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(intent::ID, PROGRAM_PATH)
+            .unwrap_or_else(|_| panic!("{PROGRAM_PATH} is missing, run `anchor build` first"));
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 100 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let mut ctx = Self { svm, admin };
+        let admin = ctx.admin.pubkey();
+        ctx.send(
+            instructions::initialize(admin, SRC_NID.to_string(), admin),
+            &[],
+        )
+        .unwrap();
+        ctx.send(instructions::set_relayer(admin, admin), &[])
+            .unwrap();
+
+        ctx
+    }
+
+    fn new_account(&mut self) -> Keypair {
+        let account = Keypair::new();
+        self.svm
+            .airdrop(&account.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        account
+    }
+
+    /// Sends `ix` paid by the admin and returns the events of the transaction
+    fn send(&mut self, ix: Instruction, signers: &[&Keypair]) -> Result<Vec<IntentEvent>, String> {
+        let mut all_signers = vec![&self.admin];
+        all_signers.extend(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.admin.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+        let account_keys = tx.message.account_keys.clone();
+
+        let meta = self
+            .svm
+            .send_transaction(tx)
+            .map_err(|err| format!("{:?}", err.err))?;
+
+        // Order and message events are emitted through self-CPI, the others in the logs
+        let mut events = parse_logs(&meta.logs);
+        events.extend(decode_inner_instructions(
+            meta.inner_instructions.iter().flatten().map(|inner| {
+                (
+                    &account_keys[inner.instruction.program_id_index as usize],
+                    &inner.instruction.data[..],
+                )
+            }),
+        ));
+
+        Ok(events)
+    }
+
+    fn config(&self) -> Config {
+        let account = self.svm.get_account(&pda::config().0).unwrap();
+        Config::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    fn lamports(&self, account: &Pubkey) -> u64 {
+        self.svm
+            .get_account(account)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn native_order(&self, creator: &Pubkey, dst_nid: &str, destination: &Pubkey) -> SwapOrder {
+        SwapOrder::new(
+            self.config().deposit_id + 1,
+            intent::ID.to_string(),
+            SRC_NID.to_string(),
+            dst_nid.to_string(),
+            creator.to_string(),
+            destination.to_string(),
+            system_program::ID.to_string(),
+            LAMPORTS_PER_SOL as u128,
+            system_program::ID.to_string(),
+            LAMPORTS_PER_SOL as u128,
+            OrderData::new(u64::MAX / 2).encode(),
+        )
+    }
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_swap_native_emits_swap_intent() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.new_account();
+    let order = ctx.native_order(&creator.pubkey(), DST_NID, &Pubkey::new_unique());
+
+    let vault_before = ctx.lamports(&pda::vault_native().0);
+    let events = ctx
+        .send(
+            instructions::swap(&order, order.id(), anchor_spl::token::ID),
+            &[&creator],
+        )
+        .unwrap();
+
+    assert_eq!(
+        ctx.lamports(&pda::vault_native().0),
+        vault_before + LAMPORTS_PER_SOL
+    );
+    assert!(ctx.svm.get_account(&pda::order(order.id()).0).is_some());
+    assert!(matches!(
+        &events[..],
+        [IntentEvent::SwapIntent(event)] if event.id == order.id() && event.dstNID == DST_NID
+    ));
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_fill_cross_chain_sends_message() {
+    let mut ctx = TestContext::new();
+    let solver = ctx.new_account();
+    let destination = Pubkey::new_unique();
+
+    let mut order = ctx.native_order(&Pubkey::new_unique(), SRC_NID, &destination);
+    order.set_src_nid(DST_NID.to_string());
+
+    let events = ctx
+        .send(
+            instructions::fill(
+                solver.pubkey(),
+                &order,
+                solver.pubkey().to_string(),
+                anchor_spl::token::ID,
+            ),
+            &[&solver],
+        )
+        .unwrap();
+
+    assert_eq!(ctx.lamports(&destination), LAMPORTS_PER_SOL);
+    assert!(events.iter().any(
+        |event| matches!(event, IntentEvent::SendMessage(message) if message.targetNetwork == DST_NID)
+    ));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, IntentEvent::OrderFilled(filled) if filled.id == order.id())));
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_recv_message_resolves_fill() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.new_account();
+    let solver = Pubkey::new_unique();
+    let order = ctx.native_order(&creator.pubkey(), DST_NID, &Pubkey::new_unique());
+
+    ctx.send(
+        instructions::swap(&order, order.id(), anchor_spl::token::ID),
+        &[&creator],
+    )
+    .unwrap();
+
+    let fill = OrderFill::new(order.id(), order.encode(), solver.to_string());
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode()).encode();
+    let relayer = ctx.admin.pubkey();
+    let ix = instructions::recv_message(
+        relayer,
+        DST_NID.to_string(),
+        1,
+        msg.clone(),
+        anchor_spl::token::ID,
+    )
+    .unwrap();
+
+    let events = ctx.send(ix.clone(), &[]).unwrap();
+
+    assert_eq!(ctx.lamports(&solver), LAMPORTS_PER_SOL);
+    assert!(ctx.svm.get_account(&pda::order(order.id()).0).is_none());
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, IntentEvent::OrderClosed(closed) if closed.id == order.id())));
+
+    // The receipt page rejects the replayed message
+    ctx.svm.expire_blockhash();
+    assert!(ctx.send(ix, &[]).is_err());
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_recv_message_rejects_fill_of_underfunded_vault() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.new_account();
+    let order = ctx.native_order(&creator.pubkey(), DST_NID, &Pubkey::new_unique());
+
+    ctx.send(
+        instructions::swap(&order, order.id(), anchor_spl::token::ID),
+        &[&creator],
+    )
+    .unwrap();
+
+    // The vault only holds its rent, paying the order out would leave it below rent
+    let vault = pda::vault_native().0;
+    let mut account = ctx.svm.get_account(&vault).unwrap();
+    account.lamports -= LAMPORTS_PER_SOL;
+    ctx.svm.set_account(vault, account).unwrap();
+
+    let solver = Pubkey::new_unique();
+    let fill = OrderFill::new(order.id(), order.encode(), solver.to_string());
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode()).encode();
+    let ix = instructions::recv_message(
+        ctx.admin.pubkey(),
+        DST_NID.to_string(),
+        1,
+        msg,
+        anchor_spl::token::ID,
+    )
+    .unwrap();
+
+    assert!(ctx.send(ix, &[]).is_err());
+    assert_eq!(ctx.lamports(&solver), 0);
+    assert!(ctx.svm.get_account(&pda::order(order.id()).0).is_some());
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_recv_message_rejects_legacy_receipt() {
+    let mut ctx = TestContext::new();
+    let creator = ctx.new_account();
+    let order = ctx.native_order(&creator.pubkey(), DST_NID, &Pubkey::new_unique());
+
+    ctx.send(
+        instructions::swap(&order, order.id(), anchor_spl::token::ID),
+        &[&creator],
+    )
+    .unwrap();
+
+    // The message was received before the receipt pages and its receipt was not migrated
+    let (receipt, bump) = pda::receipt(DST_NID, 1);
+    let mut data = vec![];
+    Receipt {
+        received: true,
+        bump,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    ctx.svm
+        .set_account(
+            receipt,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: intent::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let fill = OrderFill::new(order.id(), order.encode(), Pubkey::new_unique().to_string());
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode()).encode();
+    let ix = instructions::recv_message(
+        ctx.admin.pubkey(),
+        DST_NID.to_string(),
+        1,
+        msg,
+        anchor_spl::token::ID,
+    )
+    .unwrap();
+
+    assert!(ctx.send(ix, &[]).is_err());
+    assert!(ctx.svm.get_account(&pda::order(order.id()).0).is_some());
+}
+
+#[test]
+#[ignore = "needs target/deploy/intent.so, see the module docs"]
+fn test_admin_calls() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.pubkey();
+    let new_admin = ctx.new_account();
+
+    ctx.send(instructions::set_protocol_fee(admin, 100), &[])
+        .unwrap();
+    ctx.send(instructions::set_guardian(admin, new_admin.pubkey()), &[])
+        .unwrap();
+
+    let events = ctx
+        .send(instructions::propose_admin(admin, new_admin.pubkey()), &[])
+        .unwrap();
+    assert!(matches!(
+        &events[..],
+        [IntentEvent::AdminProposed(event)] if event.pendingAdmin == new_admin.pubkey()
+    ));
+
+    ctx.send(
+        instructions::accept_admin(new_admin.pubkey()),
+        &[&new_admin],
+    )
+    .unwrap();
+
+    let config = ctx.config();
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.guardian, new_admin.pubkey());
+    assert_eq!(config.protocol_fee, 100);
+
+    // The previous admin can no longer update the config
+    assert!(ctx
+        .send(instructions::set_protocol_fee(admin, 200), &[])
+        .is_err());
+}
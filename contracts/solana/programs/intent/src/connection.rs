@@ -1,33 +1,192 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{ed25519_program, sysvar::instructions::load_instruction_at_checked},
+};
 
 use crate::{
+    constants::ATTESTATION_DOMAIN,
     error::IntentError,
     event,
-    state::{Config, Receipt},
+    helpers::{self, EventEmitter},
+    state::{Config, ReceiptPage, VerifierSet},
 };
 
+/// Size of the signature offsets of each signature in an ed25519 precompile instruction
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Start of the signature offsets, after the signature count and padding bytes
+const ED25519_OFFSETS_START: usize = 2;
+
 pub fn send_message<'info>(
     config: &mut Account<'info, Config>,
     to: String,
     msg: Vec<u8>,
+    emitter: &EventEmitter<'_, 'info>,
 ) -> Result<()> {
     let conn_sn = config.increment_conn_sn();
 
-    emit!(event::SendMessage {
+    emitter.emit(event::SendMessage {
         targetNetwork: to,
         sn: conn_sn,
         msg,
-    });
+    })
+}
+
+/// Marks `conn_sn` as received in its receipt page. Messages received before the receipt
+/// pages have a legacy `Receipt` account until it is migrated, so `conn_sn` is also rejected
+/// while `legacy_receipt` exists.
+pub fn recv_message<'info>(
+    receipt_page: &mut Account<'info, ReceiptPage>,
+    receipt_page_bump: u8,
+    legacy_receipt: &AccountInfo<'info>,
+    conn_sn: u128,
+) -> Result<()> {
+    if receipt_page.is_received(conn_sn) || !legacy_receipt.data_is_empty() {
+        return Err(IntentError::DuplicateMessage.into());
+    }
+
+    receipt_page.set_received(conn_sn, receipt_page_bump);
 
     Ok(())
 }
 
-pub fn recv_message<'info>(receipt: &mut Account<'info, Receipt>, receipt_bump: u8) -> Result<()> {
-    if receipt.received {
-        return Err(IntentError::DuplicateMessage.into());
+/// Returns the message attested by the verifiers for a message delivered from `src_network`.
+/// The message is bound to this program and to its `network_id`, so that an attestation can
+/// not be replayed on another deployment sharing the verifier set.
+pub fn attestation_message(
+    network_id: &str,
+    src_network: &str,
+    conn_sn: u128,
+    msg: &Vec<u8>,
+) -> Vec<u8> {
+    let mut stream = rlp::RlpStream::new_list(6);
+    stream.append(&ATTESTATION_DOMAIN);
+    stream.append(&crate::id().to_bytes().to_vec());
+    stream.append(&network_id);
+    stream.append(&src_network);
+    stream.append(&conn_sn);
+    stream.append(msg);
+
+    helpers::hash_data(&stream.out())
+}
+
+/// Ensures that at least `threshold` distinct keys of the verifier set signed the attestation
+/// message through the ed25519 precompile instructions of the transaction
+pub fn verify_attestations(
+    verifier_set: &VerifierSet,
+    instructions_sysvar: &AccountInfo,
+    network_id: &str,
+    src_network: &str,
+    conn_sn: u128,
+    msg: &Vec<u8>,
+) -> Result<()> {
+    let message = attestation_message(network_id, src_network, conn_sn, msg);
+
+    let mut signers: Vec<Pubkey> = vec![];
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        if ix.program_id == ed25519_program::ID {
+            signers.extend(ed25519_signers(&ix.data, &message)?);
+        }
+        index += 1;
     }
 
-    receipt.new(receipt_bump);
+    signers.sort();
+    signers.dedup();
+
+    let attestations = signers
+        .iter()
+        .filter(|signer| verifier_set.verifiers.contains(signer))
+        .count();
+
+    if attestations < verifier_set.threshold as usize {
+        return Err(IntentError::InsufficientAttestations.into());
+    }
 
     Ok(())
 }
+
+/// Returns the public keys of the signatures over `message` in an ed25519 precompile
+/// instruction. The precompile has already verified the signatures, only the signatures
+/// carrying their key and message in the instruction itself are accepted.
+pub fn ed25519_signers(data: &[u8], message: &[u8]) -> Result<Vec<Pubkey>> {
+    let count = *data.first().ok_or(IntentError::InvalidEd25519Instruction)? as usize;
+
+    let mut signers = vec![];
+    for i in 0..count {
+        let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..start + ED25519_OFFSETS_SIZE)
+            .ok_or(IntentError::InvalidEd25519Instruction)?;
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        if read(2) != u16::MAX || read(6) != u16::MAX || read(12) != u16::MAX {
+            return Err(IntentError::InvalidEd25519Instruction.into());
+        }
+
+        let pubkey_offset = read(4) as usize;
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + 32)
+            .ok_or(IntentError::InvalidEd25519Instruction)?;
+
+        let message_offset = read(8) as usize;
+        let signed_message = data
+            .get(message_offset..message_offset + read(10) as usize)
+            .ok_or(IntentError::InvalidEd25519Instruction)?;
+
+        if signed_message == message {
+            signers.push(Pubkey::try_from(pubkey).unwrap());
+        }
+    }
+
+    Ok(signers)
+}
+
+#[cfg(test)]
+fn ed25519_instruction_data(pubkey: &Pubkey, message: &[u8], instruction_index: u16) -> Vec<u8> {
+    let pubkey_offset: u16 = 16;
+    let signature_offset: u16 = pubkey_offset + 32;
+    let message_offset: u16 = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        instruction_index,
+        pubkey_offset,
+        instruction_index,
+        message_offset,
+        message.len() as u16,
+        instruction_index,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&pubkey.to_bytes());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(message);
+
+    data
+}
+
+#[test]
+fn test_ed25519_signers() {
+    let verifier = Pubkey::new_unique();
+    let message = attestation_message("solana", "icon", 1, &vec![1, 2, 3]);
+    let data = ed25519_instruction_data(&verifier, &message, u16::MAX);
+
+    assert_eq!(ed25519_signers(&data, &message).unwrap(), vec![verifier]);
+
+    let other_message = attestation_message("solana", "icon", 2, &vec![1, 2, 3]);
+    assert!(ed25519_signers(&data, &other_message).unwrap().is_empty());
+
+    let other_network = attestation_message("sui", "icon", 1, &vec![1, 2, 3]);
+    assert!(ed25519_signers(&data, &other_network).unwrap().is_empty());
+}
+
+#[test]
+fn test_ed25519_signers_with_external_data() {
+    let verifier = Pubkey::new_unique();
+    let message = attestation_message("solana", "icon", 1, &vec![1, 2, 3]);
+    let data = ed25519_instruction_data(&verifier, &message, 0);
+
+    assert!(ed25519_signers(&data, &message).is_err());
+    assert!(ed25519_signers(&data[..10], &message).is_err());
+}
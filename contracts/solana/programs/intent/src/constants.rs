@@ -2,4 +2,47 @@ pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
 
 pub const NATIVE_ADDRESS: &str = "11111111111111111111111111111111";
 
-pub const VAULT_TOKEN_SEED_PREFIX: &'static str = "vault_token";
+/// Mint of wrapped SOL, paid out as native SOL when the order asks for it
+pub const NATIVE_MINT: &str = "So11111111111111111111111111111111111111112";
+
+pub const FEE_DENOMINATOR: u128 = 10_000;
+
+/// Layout version of the `Config` account expected by this program, see `migrate_config`
+pub const CONFIG_VERSION: u8 = 1;
+
+pub const MAX_VERIFIERS: usize = 16;
+
+/// Domain tag of the messages signed by the verifiers, see `connection::attestation_message`
+pub const ATTESTATION_DOMAIN: &str = "IntentAttestation";
+
+/// Maximum Borsh serialized size of an order stored by `swap`
+pub const MAX_ORDER_SIZE: usize = 1024;
+
+/// Accounts passed in the remaining accounts of `fill_batch` for each order: the destination
+/// address, the order finished account and the destination token account
+pub const FILL_BATCH_ORDER_ACCOUNTS: usize = 3;
+
+/// Upper bound, in seconds, on the delivery of a FILL or CANCEL message to the other network.
+/// Relayers retry undelivered messages, a message still pending after this delay has to be
+/// handled manually before the finished accounts of its order are closed.
+pub const MAX_MESSAGE_DELIVERY_DELAY: i64 = 7 * 24 * 60 * 60;
+
+/// Seconds an `OrderFinished` account is kept once its order can no longer be filled, long
+/// enough for the message settling the order to reach its source network, see
+/// `OrderFinished::is_closable`
+pub const ORDER_FINISHED_RETENTION: i64 = MAX_MESSAGE_DELIVERY_DELAY;
+
+/// Pause flags of `Config.paused`, one per instruction that can be paused
+pub const PAUSE_SWAP: u8 = 1 << 0;
+pub const PAUSE_FILL: u8 = 1 << 1;
+pub const PAUSE_CANCEL: u8 = 1 << 2;
+pub const PAUSE_RECV: u8 = 1 << 3;
+
+pub const PAUSE_ALL: u8 = PAUSE_SWAP | PAUSE_FILL | PAUSE_CANCEL | PAUSE_RECV;
+
+pub const VAULT_TOKEN_SEED_PREFIX: &str = "vault_token";
+
+pub const FEE_VAULT_SEED_PREFIX: &str = "fee_vault";
+
+/// Seed of the temporary wrapped SOL account closed to unwrap escrowed wrapped SOL
+pub const UNWRAP_SEED_PREFIX: &str = "unwrap";
@@ -79,4 +79,106 @@ pub enum IntentError {
 
     #[msg("Config account must not be specified")]
     ConfigAccountMustNotBeSpecified,
+
+    #[msg("Order data is not valid")]
+    InvalidOrderData,
+
+    #[msg("Order has expired")]
+    OrderExpired,
+
+    #[msg("Order has not expired yet")]
+    OrderNotExpired,
+
+    #[msg("Order creator account is not valid")]
+    InvalidCreatorAccount,
+
+    #[msg("Token transfer fee calculation failed")]
+    TransferFeeCalculationFailed,
+
+    #[msg("Mint account is missing")]
+    MintAccountIsMissing,
+
+    #[msg("Verifier set is not valid")]
+    InvalidVerifierSet,
+
+    #[msg("Ed25519 instruction is not valid")]
+    InvalidEd25519Instruction,
+
+    #[msg("Not enough verifier attestations")]
+    InsufficientAttestations,
+
+    #[msg("Amount exceeds the supported range")]
+    AmountOutOfRange,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Protocol fee is not valid")]
+    InvalidProtocolFee,
+
+    #[msg("Fee vault account is missing")]
+    FeeVaultAccountIsMissing,
+
+    #[msg("Only admin or guardian")]
+    OnlyGuardian,
+
+    #[msg("Instruction is paused")]
+    Paused,
+
+    #[msg("Pause flags are not valid")]
+    InvalidPauseFlags,
+
+    #[msg("Only pending admin")]
+    OnlyPendingAdmin,
+
+    #[msg("Order finished account can not be closed yet")]
+    OrderFinishedNotClosable,
+
+    #[msg("Rent payer account is not valid")]
+    InvalidRentPayer,
+
+    #[msg("Config version is not supported, the config has to be migrated")]
+    ConfigVersionMismatch,
+
+    #[msg("Config is already migrated")]
+    ConfigAlreadyMigrated,
+
+    #[msg("Order exceeds the maximum size")]
+    OrderTooLarge,
+
+    #[msg("Hook account is missing")]
+    HookAccountIsMissing,
+
+    #[msg("Hook program is not valid")]
+    InvalidHookProgram,
+
+    #[msg("Auction parameters are not valid")]
+    InvalidAuction,
+
+    #[msg("Order is exclusive to another solver")]
+    OnlyExclusiveSolver,
+
+    #[msg("Unwrap token account is missing")]
+    UnwrapTokenAccountIsMissing,
+
+    #[msg("Order can not be filled in a batch")]
+    OrderNotBatchable,
+
+    #[msg("Batch accounts do not match the orders")]
+    InvalidBatchAccounts,
+
+    #[msg("Order cancel was already requested")]
+    CancelAlreadyRequested,
+
+    #[msg("Token-2022 mints with a transfer hook are not supported")]
+    TransferHookNotSupported,
+
+    #[msg("Partial fills are not supported")]
+    PartialFillNotSupported,
+
+    #[msg("Account does not hold enough lamports above rent")]
+    InsufficientFunds,
+
+    #[msg("Cancel request can not be closed before the order is settled")]
+    CancelRequestNotClosable,
 }
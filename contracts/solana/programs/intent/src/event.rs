@@ -2,6 +2,8 @@
 
 use anchor_lang::prelude::*;
 
+use crate::types::misc::HookStatus;
+
 /// Emitted when a new swap intent is created
 #[event]
 pub struct SwapIntent {
@@ -36,6 +38,10 @@ pub struct OrderFilled {
     pub id: u128,
     // The source network ID of the swap order
     pub srcNID: String,
+    // The amount of `toToken` delivered, which decays from the start amount for auctions
+    pub toAmount: u128,
+    // The outcome of the post-fill hook of the order
+    pub hookStatus: HookStatus,
 }
 
 // Emitted when a swap order is cancelled
@@ -54,6 +60,76 @@ pub struct OrderClosed {
     pub id: u128,
 }
 
+/// Emitted when a new admin is proposed
+#[event]
+pub struct AdminProposed {
+    // The current admin
+    pub admin: Pubkey,
+    // The proposed admin, which has to accept the role
+    pub pendingAdmin: Pubkey,
+}
+
+/// Emitted when the proposed admin accepts the admin role
+#[event]
+pub struct AdminChanged {
+    // The previous admin
+    pub oldAdmin: Pubkey,
+    // The new admin
+    pub newAdmin: Pubkey,
+}
+
+/// Emitted when the relayer is changed
+#[event]
+pub struct RelayerChanged {
+    // The previous relayer
+    pub oldRelayer: Pubkey,
+    // The new relayer
+    pub newRelayer: Pubkey,
+}
+
+/// Emitted when the fee handler is changed
+#[event]
+pub struct FeeHandlerChanged {
+    // The previous fee handler
+    pub oldFeeHandler: Pubkey,
+    // The new fee handler
+    pub newFeeHandler: Pubkey,
+}
+
+/// Emitted when the guardian is changed
+#[event]
+pub struct GuardianChanged {
+    // The previous guardian
+    pub oldGuardian: Pubkey,
+    // The new guardian
+    pub newGuardian: Pubkey,
+}
+
+/// Emitted when the config account is migrated to a new layout version
+#[event]
+pub struct ConfigMigrated {
+    // The layout version of the migrated config
+    pub version: u8,
+}
+
+/// Emitted when instructions are paused
+#[event]
+pub struct Paused {
+    // The account that paused the instructions
+    pub authority: Pubkey,
+    // Bitmask of the instructions paused by this call
+    pub flags: u8,
+}
+
+/// Emitted when instructions are unpaused
+#[event]
+pub struct Unpaused {
+    // The account that unpaused the instructions
+    pub authority: Pubkey,
+    // Bitmask of the instructions unpaused by this call
+    pub flags: u8,
+}
+
 /// Emitted when a cross-chain message is sent
 #[event]
 pub struct SendMessage {
@@ -1,10 +1,68 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::{hash, keccak, program::invoke, system_instruction},
+    solana_program::{
+        hash,
+        instruction::Instruction,
+        keccak,
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
 };
-use anchor_spl::token;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions,
+            StateWithExtensions,
+        },
+    },
+    token_interface::{
+        close_account, sync_native, transfer_checked, CloseAccount, Mint, SyncNative,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    constants::FEE_DENOMINATOR,
+    error::IntentError,
+    state::Config,
+    types::{misc::HookStatus, order_data::OrderHook},
+};
+
+/// Emits events through a self-CPI signed by the event authority of an `#[event_cpi]`
+/// context, so that they are recorded in the instruction data rather than in the truncatable
+/// program logs. Mirrors `emit_cpi!` for the functions not taking the context.
+pub struct EventEmitter<'a, 'info> {
+    event_authority: &'a AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'a, 'info> EventEmitter<'a, 'info> {
+    pub fn new(event_authority: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            event_authority,
+            bump,
+        }
+    }
+
+    pub fn emit<T: anchor_lang::Event>(&self, event: T) -> Result<()> {
+        let mut data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+        data.extend(event.data());
+
+        let ix = Instruction::new_with_bytes(
+            crate::id(),
+            &data,
+            vec![AccountMeta::new_readonly(self.event_authority.key(), true)],
+        );
+        invoke_signed(
+            &ix,
+            &[self.event_authority.to_owned()],
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
 
-use crate::state::Config;
+        Ok(())
+    }
+}
 
 pub fn transfer_sol<'info>(
     from: &AccountInfo<'info>,
@@ -21,13 +79,52 @@ pub fn transfer_sol<'info>(
     Ok(())
 }
 
+/// Moves `amount` lamports out of the program owned account `from`, which has to stay rent
+/// exempt after the debit
 pub fn transfer_sol_signed<'info>(
     from: &AccountInfo<'info>,
     to: &AccountInfo<'info>,
     amount: u64,
 ) -> Result<()> {
-    **from.try_borrow_mut_lamports()? -= amount;
-    **to.try_borrow_mut_lamports()? += amount;
+    let rent = Rent::get()?.minimum_balance(from.data_len());
+    let from_lamports = from
+        .lamports()
+        .checked_sub(amount)
+        .filter(|lamports| *lamports >= rent)
+        .ok_or(IntentError::InsufficientFunds)?;
+    let to_lamports = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(IntentError::ArithmeticOverflow)?;
+
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+
+    Ok(())
+}
+
+/// Resizes a program owned account, topping up the rent from `payer` when it grows and
+/// returning the excess rent to `refund` when it shrinks
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    new_size: usize,
+    payer: &AccountInfo<'info>,
+    refund: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(new_size);
+    let lamports = account.lamports();
+
+    if rent > lamports {
+        transfer_sol(payer, account, rent - lamports, system_program)?;
+    }
+
+    account.realloc(new_size, false)?;
+
+    // The excess rent is only returned once the account is shrunk
+    if lamports > rent {
+        transfer_sol_signed(account, refund, lamports - rent)?;
+    }
 
     Ok(())
 }
@@ -36,39 +133,180 @@ pub fn transfer_spl_token<'info>(
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
     authority: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
     amount: u64,
     token_program: AccountInfo<'info>,
 ) -> Result<()> {
-    let accounts = token::Transfer {
+    ensure_no_transfer_hook(mint)?;
+
+    let accounts = TransferChecked {
         from,
+        mint: mint.to_account_info(),
         to,
         authority,
     };
-    token::transfer(CpiContext::new(token_program, accounts), amount)
+    transfer_checked(
+        CpiContext::new(token_program, accounts),
+        amount,
+        mint.decimals,
+    )
 }
 
 pub fn transfer_spl_token_signed<'info>(
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
     authority: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
     amount: u64,
     token_program: AccountInfo<'info>,
     config: &Account<'info, Config>,
 ) -> Result<()> {
-    let accounts = token::Transfer {
+    ensure_no_transfer_hook(mint)?;
+
+    let accounts = TransferChecked {
         from,
+        mint: mint.to_account_info(),
         to,
         authority,
     };
 
     let seeds = &[Config::SEED_PREFIX.as_bytes(), &[config.bump]];
     let signer_seeds = &[&seeds[..]];
-    token::transfer(
+    transfer_checked(
         CpiContext::new_with_signer(token_program, accounts, signer_seeds),
         amount,
+        mint.decimals,
     )
 }
 
+/// Syncs the token amount of a wrapped SOL account with the lamports transferred into it
+pub fn sync_wrapped_sol<'info>(
+    account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    sync_native(CpiContext::new(token_program, SyncNative { account }))
+}
+
+/// Closes a token account owned by `config`, sending its lamports to `destination`
+pub fn close_token_account_signed<'info>(
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    config: &Account<'info, Config>,
+) -> Result<()> {
+    let accounts = CloseAccount {
+        account,
+        destination,
+        authority: config.to_account_info(),
+    };
+
+    let seeds = &[Config::SEED_PREFIX.as_bytes(), &[config.bump]];
+    let signer_seeds = &[&seeds[..]];
+    close_account(CpiContext::new_with_signer(
+        token_program,
+        accounts,
+        signer_seeds,
+    ))
+}
+
+/// Rejects Token-2022 mints with an active transfer hook. Transfers of these mints invoke the
+/// hook program with the extra accounts of its validation account, which the instructions do
+/// not resolve, so they are refused up front with a clear error instead of failing inside the
+/// token program. A transfer hook extension without a hook program is accepted.
+pub fn ensure_no_transfer_hook(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    match mint_state.get_extension::<TransferHook>() {
+        Ok(hook) if Option::<Pubkey>::from(hook.program_id).is_some() => {
+            Err(IntentError::TransferHookNotSupported.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the amount that has to be sent so that the recipient receives exactly `amount`,
+/// grossing it up by the current epoch fee of the Token-2022 transfer fee extension. Mints
+/// without the extension, including legacy SPL token mints, return `amount` unchanged.
+pub fn amount_with_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .get_epoch_fee(Clock::get()?.epoch)
+            .calculate_pre_fee_amount(amount)
+            .ok_or(IntentError::TransferFeeCalculationFailed.into()),
+        Err(_) => Ok(amount),
+    }
+}
+
+/// Invokes the post-fill hook of an order with the accounts found in `remaining_accounts`.
+/// The hook accounts never carry signer privileges. A failed CPI aborts the transaction, so
+/// a hook that allows failure is skipped when its program account is not passed instead.
+pub fn execute_hook<'info>(
+    hook: Option<&OrderHook>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<HookStatus> {
+    let hook = match hook {
+        Some(hook) => hook,
+        None => return Ok(HookStatus::None),
+    };
+
+    let find_account = |key: &Pubkey| remaining_accounts.iter().find(|account| account.key == key);
+
+    let program = match find_account(&hook.program_id()) {
+        Some(program) => program,
+        None if hook.allow_failure() => return Ok(HookStatus::Skipped),
+        None => return Err(IntentError::HookAccountIsMissing.into()),
+    };
+    if !program.executable || program.key() == crate::id() {
+        return Err(IntentError::InvalidHookProgram.into());
+    }
+
+    let mut account_metas = vec![];
+    let mut account_infos = vec![program.to_account_info()];
+    for account in hook.accounts() {
+        let info = find_account(&account.pubkey).ok_or(IntentError::HookAccountIsMissing)?;
+
+        if account.is_writable {
+            account_metas.push(AccountMeta::new(account.pubkey, false));
+        } else {
+            account_metas.push(AccountMeta::new_readonly(account.pubkey, false));
+        }
+        account_infos.push(info.to_account_info());
+    }
+
+    let ix = Instruction {
+        program_id: hook.program_id(),
+        accounts: account_metas,
+        data: hook.data(),
+    };
+    invoke(&ix, &account_infos)?;
+
+    Ok(HookStatus::Executed)
+}
+
+/// Converts an order amount to a token amount, rejecting amounts above `u64::MAX`
+pub fn to_u64(amount: u128) -> Result<u64> {
+    u64::try_from(amount).map_err(|_| IntentError::AmountOutOfRange.into())
+}
+
+/// Splits `amount` into the protocol fee and the remaining amount
+pub fn split_protocol_fee(amount: u128, protocol_fee: u64) -> Result<(u128, u128)> {
+    let fee = amount
+        .checked_mul(protocol_fee as u128)
+        .ok_or(IntentError::ArithmeticOverflow)?
+        / FEE_DENOMINATOR;
+    let remaining = amount
+        .checked_sub(fee)
+        .ok_or(IntentError::ArithmeticOverflow)?;
+
+    Ok((fee, remaining))
+}
+
 pub fn get_instruction_data(ix_name: &str, data: Vec<u8>) -> Vec<u8> {
     let preimage = format!("{}:{}", "global", ix_name);
 
@@ -82,6 +320,25 @@ pub fn get_instruction_data(ix_name: &str, data: Vec<u8>) -> Vec<u8> {
     ix_data
 }
 
-pub fn hash_data(data: &Vec<u8>) -> Vec<u8> {
-    keccak::hash(&data).to_bytes().to_vec()
+pub fn hash_data(data: &[u8]) -> Vec<u8> {
+    keccak::hash(data).to_bytes().to_vec()
+}
+
+#[test]
+fn test_to_u64() {
+    assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+    assert!(to_u64(u64::MAX as u128 + 1).is_err());
+}
+
+#[test]
+fn test_split_protocol_fee() {
+    assert_eq!(
+        split_protocol_fee(1_000_000, 100).unwrap(),
+        (10_000, 990_000)
+    );
+    assert_eq!(
+        split_protocol_fee(u64::MAX as u128 * 2, 0).unwrap().1,
+        u64::MAX as u128 * 2
+    );
+    assert!(split_protocol_fee(u128::MAX, 2).is_err());
 }
@@ -2,8 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::{
     connection,
+    constants::*,
     error::*,
-    event, helpers,
+    event,
+    helpers::{self, EventEmitter},
     state::*,
     types::{
         order_cancel::Cancel,
@@ -14,44 +16,89 @@ use crate::{
 };
 
 pub fn cancel_order<'info>(ctx: Context<'_, '_, '_, 'info, CancelCtx<'info>>) -> Result<()> {
+    send_cancel(
+        ctx.accounts.signer.key(),
+        &ctx.accounts.order_account.order,
+        &mut ctx.accounts.config,
+        ctx.accounts.order_finished.as_mut(),
+        ctx.bumps.order_finished,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+pub fn expire_order<'info>(ctx: Context<'_, '_, '_, 'info, ExpireCtx<'info>>) -> Result<()> {
     let order = &ctx.accounts.order_account.order;
-    let config = &mut ctx.accounts.config;
+
+    let order_data = order.order_data().unwrap_or_default();
+    if !order_data.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(IntentError::OrderNotExpired.into());
+    }
+
+    if ctx.accounts.cancel_request.requested {
+        return Err(IntentError::CancelAlreadyRequested.into());
+    }
+    ctx.accounts
+        .cancel_request
+        .new(ctx.accounts.signer.key(), ctx.bumps.cancel_request);
+
+    send_cancel(
+        ctx.accounts.signer.key(),
+        order,
+        &mut ctx.accounts.config,
+        ctx.accounts.order_finished.as_mut(),
+        ctx.bumps.order_finished,
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )
+}
+
+/// Resolves the cancel right away for same chain orders, otherwise sends the cancel
+/// message to the destination chain which refunds the creator through `resolve_cancel`
+pub fn send_cancel<'info>(
+    payer: Pubkey,
+    order: &SwapOrder,
+    config: &mut Account<'info, Config>,
+    order_finished: Option<&mut Account<'info, OrderFinished>>,
+    order_finished_bump: Option<u8>,
+    emitter: &EventEmitter<'_, 'info>,
+) -> Result<()> {
+    config.ensure_not_paused(PAUSE_CANCEL)?;
 
     let cancel = Cancel::new(order.encode());
 
     if order.src_nid() == order.dst_nid() {
-        let order_finished = ctx
-            .accounts
-            .order_finished
-            .as_mut()
-            .ok_or(IntentError::OrderFinishedAccountIsMissing)?;
+        let order_finished = order_finished.ok_or(IntentError::OrderFinishedAccountIsMissing)?;
 
         resolve_cancel(
+            payer,
             config.network_id.clone(),
             cancel,
             order,
             config,
             order_finished,
-            ctx.bumps.order_finished.unwrap(),
+            order_finished_bump.unwrap(),
+            emitter,
         )?;
         return Ok(());
     }
 
-    if ctx.accounts.order_finished.is_some() {
+    if order_finished.is_some() {
         return Err(IntentError::OrderFinishedAccountMustNotBeSpecified.into());
     }
 
     let order_msg = OrderMessage::new(MessageType::CANCEL, cancel.encode());
-    connection::send_message(config, order.dst_nid(), order_msg.encode())
+    connection::send_message(config, order.dst_nid(), order_msg.encode(), emitter)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn resolve_cancel<'info>(
+    payer: Pubkey,
     src_network: String,
     cancel: Cancel,
     order: &SwapOrder,
     config: &mut Account<'info, Config>,
     order_finished: &mut Account<'info, OrderFinished>,
     order_finished_bump: u8,
+    emitter: &EventEmitter<'_, 'info>,
 ) -> Result<()> {
     if src_network != order.src_nid() {
         return Err(IntentError::InvalidNetwork.into());
@@ -60,21 +107,20 @@ pub fn resolve_cancel<'info>(
     if order_finished.finished {
         return Ok(());
     }
-    order_finished.new(order_finished_bump);
+    order_finished.new(payer, order_finished_bump, Clock::get()?.unix_timestamp);
 
     let fill = OrderFill::new(order.id(), cancel.order_bytes(), order.creator());
     let order_msg = OrderMessage::new(MessageType::FILL, fill.encode());
 
-    connection::send_message(config, order.src_nid(), order_msg.encode())?;
+    connection::send_message(config, order.src_nid(), order_msg.encode(), emitter)?;
 
-    emit!(event::OrderCancelled {
+    emitter.emit(event::OrderCancelled {
         id: order.id(),
         srcNID: order.src_nid(),
-    });
-
-    Ok(())
+    })
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(order: SwapOrder)]
 pub struct CancelCtx<'info> {
@@ -86,17 +132,15 @@ pub struct CancelCtx<'info> {
 
     pub system_program: Program<'info, System>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
     pub config: Account<'info, Config>,
 
     #[account(
         mut,
-        seeds = [
-            &signer.key().to_bytes(),
-            order.dst_nid().as_bytes(),
-            &order.amount().to_be_bytes(),
-            &order.to_amount().to_be_bytes()
-        ],
+        seeds = [OrderAccount::SEED_PREFIX.as_bytes(), &order.id().to_be_bytes()],
         bump = order_account.bump
     )]
     pub order_account: Account<'info, OrderAccount>,
@@ -111,6 +155,51 @@ pub struct CancelCtx<'info> {
     pub order_finished: Option<Account<'info, OrderFinished>>,
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order: SwapOrder)]
+pub struct ExpireCtx<'info> {
+    /// Any account can expire an order once its deadline has passed
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [OrderAccount::SEED_PREFIX.as_bytes(), &order.id().to_be_bytes()],
+        bump = order_account.bump,
+        constraint = order_account.order == order @IntentError::OrderMismatched
+    )]
+    pub order_account: Account<'info, OrderAccount>,
+
+    /// Marker of the cancel sent by a previous `expire`, which is rejected
+    #[account(
+        init_if_needed,
+        space = CancelRequest::SIZE,
+        payer = signer,
+        seeds = [CancelRequest::SEED_PREFIX.as_bytes(), &order.id().to_be_bytes()],
+        bump
+    )]
+    pub cancel_request: Account<'info, CancelRequest>,
+
+    #[account(
+        init_if_needed,
+        space = OrderFinished::SIZE,
+        payer = signer,
+        seeds = [&order.get_hash()],
+        bump
+      )]
+    pub order_finished: Option<Account<'info, OrderFinished>>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(src_network: String, cancel: Cancel)]
 pub struct ResolveCancelCtx<'info> {
@@ -124,7 +213,10 @@ pub struct ResolveCancelCtx<'info> {
     )]
     pub intent: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
     pub config: Account<'info, Config>,
 
     #[account(
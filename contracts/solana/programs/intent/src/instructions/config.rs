@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{state::*, IntentError};
+use crate::{event, helpers, state::*, IntentError};
 
 pub fn initialize(
     ctx: Context<InitializeCtx>,
@@ -20,25 +20,123 @@ pub fn initialize(
     Ok(())
 }
 
-pub fn set_admin(ctx: Context<SetAdminCtx>, account: Pubkey) -> Result<()> {
-    ctx.accounts.config.set_admin(account);
+pub fn propose_admin(ctx: Context<ProposeAdminCtx>, account: Pubkey) -> Result<()> {
+    ctx.accounts.config.propose_admin(account);
+
+    emit!(event::AdminProposed {
+        admin: ctx.accounts.admin.key(),
+        pendingAdmin: account
+    });
 
     Ok(())
 }
 
-pub fn set_protocol_fee(ctx: Context<SetFeeCtx>, fee: u64) -> Result<()> {
-    ctx.accounts.config.set_protocol_fee(fee);
+pub fn accept_admin(ctx: Context<AcceptAdminCtx>) -> Result<()> {
+    let old_admin = ctx.accounts.config.accept_admin();
+
+    emit!(event::AdminChanged {
+        oldAdmin: old_admin,
+        newAdmin: ctx.accounts.pending_admin.key()
+    });
+
+    Ok(())
+}
+
+pub fn set_relayer(ctx: Context<SetRelayerCtx>, relayer: Pubkey) -> Result<()> {
+    let old_relayer = ctx.accounts.config.relayer;
+    ctx.accounts.config.set_relayer(relayer);
+
+    emit!(event::RelayerChanged {
+        oldRelayer: old_relayer,
+        newRelayer: relayer
+    });
 
     Ok(())
 }
 
+pub fn set_protocol_fee(ctx: Context<SetFeeCtx>, fee: u64) -> Result<()> {
+    ctx.accounts.config.set_protocol_fee(fee)
+}
+
 pub fn set_fee_handler(ctx: Context<SetFeeHandlerCtx>, fee_handler: Pubkey) -> Result<()> {
+    let old_fee_handler = ctx.accounts.config.fee_handler;
     ctx.accounts.config.set_fee_handler(fee_handler);
 
+    emit!(event::FeeHandlerChanged {
+        oldFeeHandler: old_fee_handler,
+        newFeeHandler: fee_handler
+    });
+
+    Ok(())
+}
+
+pub fn set_guardian(ctx: Context<SetGuardianCtx>, guardian: Pubkey) -> Result<()> {
+    let old_guardian = ctx.accounts.config.guardian;
+    ctx.accounts.config.set_guardian(guardian);
+
+    emit!(event::GuardianChanged {
+        oldGuardian: old_guardian,
+        newGuardian: guardian
+    });
+
+    Ok(())
+}
+
+pub fn pause(ctx: Context<PauseCtx>, flags: u8) -> Result<()> {
+    ctx.accounts.config.pause(flags)?;
+
+    emit!(event::Paused {
+        authority: ctx.accounts.authority.key(),
+        flags
+    });
+
+    Ok(())
+}
+
+pub fn unpause(ctx: Context<UnpauseCtx>, flags: u8) -> Result<()> {
+    ctx.accounts.config.unpause(flags)?;
+
+    emit!(event::Unpaused {
+        authority: ctx.accounts.admin.key(),
+        flags
+    });
+
+    Ok(())
+}
+
+pub fn set_verifier_set(
+    ctx: Context<SetVerifierSetCtx>,
+    verifiers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    ctx.accounts
+        .verifier_set
+        .set(verifiers, threshold, ctx.bumps.verifier_set)
+}
+
+pub fn migrate_config(ctx: Context<MigrateConfigCtx>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let config = Config::migrate(&config_info.try_borrow_data()?)?;
+    config.ensure_admin(ctx.accounts.admin.key())?;
+
+    helpers::resize_account(
+        &config_info,
+        Config::size(&config.network_id),
+        &ctx.accounts.admin,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    emit!(event::ConfigMigrated {
+        version: config.version
+    });
+
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(network_id: String)]
 pub struct InitializeCtx<'info> {
     /// The configuration account, which stores important settings for the program.
     /// This account is initialized only once during the lifetime of program and it will
@@ -46,7 +144,7 @@ pub struct InitializeCtx<'info> {
     #[account(
         init,
         payer = signer,
-        space = Config::SIZE,
+        space = Config::size(&network_id),
         seeds = [Config::SEED_PREFIX.as_bytes()],
         bump
     )]
@@ -71,24 +169,88 @@ pub struct InitializeCtx<'info> {
     system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateConfigCtx<'info> {
+    /// CHECK: The configuration account, which may hold any earlier layout version. It is
+    /// deserialized and reallocated in the instruction.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the admin stored in the config to ensure it is valid.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The solana system program account, used for creating and managing accounts.
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct GetConfigCtx<'info> {
     /// The configuration account, which stores important settings for the program.
     #[account(
         seeds = [Config::SEED_PREFIX.as_bytes()],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the pending admin of the program will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
     )]
     pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.admin @IntentError::OnlyAdmin
+    )]
+    pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SetAdminCtx<'info> {
+pub struct AcceptAdminCtx<'info> {
     /// The configuration account, which stores important settings for the program.
     /// This account is mutable because the admin of the program will be updated.
     #[account(
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.pending_admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.pending_admin @IntentError::OnlyPendingAdmin
+    )]
+    pub pending_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the relayer of the program will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
     )]
     pub config: Account<'info, Config>,
 
@@ -108,7 +270,71 @@ pub struct SetFeeHandlerCtx<'info> {
     #[account(
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.admin @IntentError::OnlyAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardianCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the guardian of the program will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.admin @IntentError::OnlyAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the pause flags will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` and `config.guardian` to ensure it is valid.
+    #[account(
+        mut,
+        constraint = config.can_pause(authority.key()) @IntentError::OnlyGuardian
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnpauseCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    /// This account is mutable because the pause flags will be updated.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
     )]
     pub config: Account<'info, Config>,
 
@@ -128,10 +354,40 @@ pub struct SetFeeCtx<'info> {
     #[account(
         mut,
         seeds = [Config::SEED_PREFIX.as_bytes()],
-        bump = config.bump
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The account that signs and pays for the transaction. This account is checked
+    /// against the `config.admin` to ensure it is valid.
+    #[account(
+        mut,
+        address = config.admin @IntentError::OnlyAdmin
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetVerifierSetCtx<'info> {
+    /// The configuration account, which stores important settings for the program.
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
     )]
     pub config: Account<'info, Config>,
 
+    /// The verifier set account, which is replaced by the new set of verifiers.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = VerifierSet::SIZE,
+        seeds = [VerifierSet::SEED_PREFIX.as_bytes()],
+        bump
+    )]
+    pub verifier_set: Account<'info, VerifierSet>,
+
     /// The account that signs and pays for the transaction. This account is checked
     /// against the `config.admin` to ensure it is valid.
     #[account(
@@ -139,4 +395,7 @@ pub struct SetFeeCtx<'info> {
         address = config.admin @IntentError::OnlyAdmin
     )]
     pub admin: Signer<'info>,
+
+    /// The solana system program account, used for creating and managing accounts.
+    pub system_program: Program<'info, System>,
 }
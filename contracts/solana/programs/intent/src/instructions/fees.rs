@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::*, error::*, helpers::*, state::*};
+
+pub fn claim_fees(ctx: Context<ClaimFeesCtx>, mint: Pubkey) -> Result<()> {
+    let fee_vault = &ctx.accounts.fee_vault;
+    let amount = accrued_fees(fee_vault, mint)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if mint == Pubkey::from_str(NATIVE_ADDRESS).unwrap() {
+        transfer_sol_signed(fee_vault, &ctx.accounts.fee_handler, amount)?;
+    } else {
+        let fee_handler_token_account = ctx
+            .accounts
+            .fee_handler_token_account
+            .as_ref()
+            .ok_or(IntentError::FeeHandlerTokenAccountIsMissing)?;
+
+        let token_mint = ctx
+            .accounts
+            .token_mint
+            .as_ref()
+            .ok_or(IntentError::MintAccountIsMissing)?;
+
+        transfer_spl_token_signed(
+            fee_vault.to_account_info(),
+            fee_handler_token_account.to_account_info(),
+            ctx.accounts.config.to_account_info(),
+            token_mint,
+            amount,
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.config,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn query_accrued_fees(ctx: Context<QueryAccruedFeesCtx>, mint: Pubkey) -> Result<u64> {
+    accrued_fees(&ctx.accounts.fee_vault, mint)
+}
+
+/// Returns the protocol fees accrued in the fee vault of `mint`, which is the balance above
+/// rent for native SOL and the token balance otherwise
+fn accrued_fees(fee_vault: &AccountInfo, mint: Pubkey) -> Result<u64> {
+    if fee_vault.data_is_empty() {
+        return Ok(0);
+    }
+
+    // The fee vault is a PDA of the program, so it can only hold a fee vault account
+    let data = fee_vault.try_borrow_data()?;
+    if mint == Pubkey::from_str(NATIVE_ADDRESS).unwrap() {
+        VaultNative::try_deserialize(&mut &data[..])?;
+
+        let rent = Rent::get()?.minimum_balance(fee_vault.data_len());
+        return Ok(fee_vault.lamports().saturating_sub(rent));
+    }
+
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ClaimFeesCtx<'info> {
+    /// The fee handler claiming the accrued protocol fees
+    #[account(
+        mut,
+        address = config.fee_handler @ IntentError::InvalidFeeHandler
+    )]
+    pub fee_handler: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The configuration account, which is the authority of the fee vaults.
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The fee vault of `mint`, a native vault account for SOL and a token account
+    /// otherwise. The account is validated in the instruction.
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &mint.to_bytes()],
+        bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    /// The associated token account of the fee handler receiving the token fees
+    #[account(
+        init_if_needed,
+        payer = fee_handler,
+        associated_token::mint = token_mint,
+        associated_token::authority = fee_handler,
+        associated_token::token_program = token_program
+    )]
+    pub fee_handler_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = mint @IntentError::MintAccountMismatch,
+        mint::token_program = token_program
+    )]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct QueryAccruedFeesCtx<'info> {
+    /// CHECK: The fee vault of `mint`, which may not be initialized yet. The account is
+    /// validated in the instruction.
+    #[account(
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &mint.to_bytes()],
+        bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+}
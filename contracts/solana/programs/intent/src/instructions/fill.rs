@@ -3,7 +3,7 @@ use std::str::FromStr;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
@@ -30,6 +30,8 @@ pub fn order_fill<'info>(
     let config = &mut ctx.accounts.config;
     let order_fnished = &mut ctx.accounts.order_finished;
 
+    config.ensure_not_paused(PAUSE_FILL)?;
+
     if order.dst_address() != ctx.accounts.destination_address.key().to_string() {
         return Err(IntentError::InvalidDestinationAccount.into());
     }
@@ -37,24 +39,47 @@ pub fn order_fill<'info>(
     if order_fnished.finished {
         return Err(IntentError::OrderAlreadyFilled.into());
     }
-    order_fnished.new(ctx.bumps.order_finished);
 
-    let fee = (order.to_amount() as u64 * config.protocol_fee) / 10_000;
-    let to_amount = order.to_amount() as u64 - fee;
+    let now = Clock::get()?.unix_timestamp;
+    let order_data = order.order_data().unwrap_or_default();
+    if order_data.is_expired(now) {
+        return Err(IntentError::OrderExpired.into());
+    }
+    order_fnished.new(ctx.accounts.signer.key(), ctx.bumps.order_finished, now);
 
-    if order.to_token() == NATIVE_ADDRESS {
+    let fill_amount = order_data.fill_amount(order.to_amount(), now)?;
+    let (fee, to_amount) = split_protocol_fee(fill_amount, config.protocol_fee)?;
+    let fee = to_u64(fee)?;
+    let to_amount = to_u64(to_amount)?;
+
+    // Native SOL and wrapped SOL are delivered in the form asked by the order, the solver
+    // pays lamports whenever SOL is wrapped or unwrapped on the way
+    let payout_token = order_data.payout_token(&order.to_token());
+
+    if payout_token == NATIVE_ADDRESS {
         transfer_sol(
             &ctx.accounts.signer,
             &ctx.accounts.destination_address,
             to_amount,
             &ctx.accounts.system_program,
         )?;
+    } else if order.to_token() == NATIVE_ADDRESS {
+        let destination_token_account = ctx
+            .accounts
+            .destination_token_account
+            .as_ref()
+            .ok_or(IntentError::CreatorTokenAccountIsMissing)?;
+
         transfer_sol(
             &ctx.accounts.signer,
-            &ctx.accounts.fee_handler,
-            fee,
+            &destination_token_account.to_account_info(),
+            to_amount,
             &ctx.accounts.system_program,
         )?;
+        sync_wrapped_sol(
+            destination_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
     } else {
         let signer_token_account = ctx
             .accounts
@@ -68,31 +93,92 @@ pub fn order_fill<'info>(
             .as_ref()
             .ok_or(IntentError::CreatorTokenAccountIsMissing)?;
 
-        let fee_handler_token_account = ctx
+        let mint = ctx
             .accounts
-            .fee_handler_token_account
+            .mint
             .as_ref()
-            .ok_or(IntentError::FeeHandlerTokenAccountIsMissing)?;
+            .ok_or(IntentError::MintAccountIsMissing)?;
 
+        // The solver pays any transfer fee so that the destination receives the full amount
         transfer_spl_token(
             signer_token_account.to_account_info(),
             destination_token_account.to_account_info(),
             ctx.accounts.signer.to_account_info(),
-            to_amount,
+            mint,
+            amount_with_transfer_fee(mint, to_amount)?,
             ctx.accounts.token_program.to_account_info(),
         )?;
-        transfer_spl_token(
-            signer_token_account.to_account_info(),
-            fee_handler_token_account.to_account_info(),
-            ctx.accounts.signer.to_account_info(),
+    }
+
+    // The protocol fee accrues in the fee vault of `to_token` whatever form it was delivered in
+    if order.to_token() == NATIVE_ADDRESS {
+        let fee_vault_native = ctx
+            .accounts
+            .fee_vault_native
+            .as_mut()
+            .ok_or(IntentError::FeeVaultAccountIsMissing)?;
+        fee_vault_native.new(ctx.bumps.fee_vault_native.unwrap());
+
+        transfer_sol(
+            &ctx.accounts.signer,
+            &fee_vault_native.to_account_info(),
             fee,
-            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program,
         )?;
+    } else {
+        let fee_vault_token_account = ctx
+            .accounts
+            .fee_vault_token_account
+            .as_ref()
+            .ok_or(IntentError::FeeVaultAccountIsMissing)?;
+
+        if payout_token == NATIVE_ADDRESS {
+            transfer_sol(
+                &ctx.accounts.signer,
+                &fee_vault_token_account.to_account_info(),
+                fee,
+                &ctx.accounts.system_program,
+            )?;
+            sync_wrapped_sol(
+                fee_vault_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        } else {
+            let signer_token_account = ctx
+                .accounts
+                .signer_token_account
+                .as_ref()
+                .ok_or(IntentError::SignerTokenAccountIsMissing)?;
+
+            let mint = ctx
+                .accounts
+                .mint
+                .as_ref()
+                .ok_or(IntentError::MintAccountIsMissing)?;
+
+            transfer_spl_token(
+                signer_token_account.to_account_info(),
+                fee_vault_token_account.to_account_info(),
+                ctx.accounts.signer.to_account_info(),
+                mint,
+                amount_with_transfer_fee(mint, fee)?,
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        }
     }
 
+    let hook_status = execute_hook(order_data.hook(), ctx.remaining_accounts)?;
+
     let fill = OrderFill::new(order.id(), order.encode(), solver_address);
 
     if order.src_nid() == order.dst_nid() {
+        emit_cpi!(event::OrderFilled {
+            id: order.id(),
+            srcNID: order.src_nid(),
+            toAmount: fill_amount,
+            hookStatus: hook_status,
+        });
+
         invoke_resolve(
             config.network_id.clone(),
             Resolve::Fill(fill),
@@ -107,11 +193,18 @@ pub fn order_fill<'info>(
     }
 
     let order_msg = OrderMessage::new(MessageType::FILL, fill.encode());
-    connection::send_message(config, order.src_nid(), order_msg.encode())?;
-
-    emit!(event::OrderFilled {
+    connection::send_message(
+        config,
+        order.src_nid(),
+        order_msg.encode(),
+        &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    )?;
+
+    emit_cpi!(event::OrderFilled {
         id: order.id(),
         srcNID: order.src_nid(),
+        toAmount: fill_amount,
+        hookStatus: hook_status,
     });
 
     Ok(())
@@ -132,6 +225,13 @@ pub fn resolve_fill(
         return Err(IntentError::InvalidNetwork.into());
     }
 
+    // Orders are released as a whole, a partial fill would pay the solver the full escrow
+    if fill.amount().is_some() {
+        return Err(IntentError::PartialFillNotSupported.into());
+    }
+
+    let payout_token = order.payout_token();
+
     if order.token() == NATIVE_ADDRESS {
         let native_vault_account = ctx
             .accounts
@@ -139,11 +239,30 @@ pub fn resolve_fill(
             .as_mut()
             .ok_or(IntentError::NativeVaultAccountIsMissing)?;
 
-        transfer_sol_signed(
-            &native_vault_account.to_account_info(),
-            &ctx.accounts.solver.to_account_info(),
-            order.amount() as u64,
-        )?;
+        if payout_token == NATIVE_ADDRESS {
+            transfer_sol_signed(
+                &native_vault_account.to_account_info(),
+                &ctx.accounts.solver.to_account_info(),
+                to_u64(order.amount())?,
+            )?;
+        } else {
+            let solver_token_account = ctx
+                .accounts
+                .solver_token_account
+                .as_ref()
+                .ok_or(IntentError::SolverTokenAccountIsMissing)?;
+
+            // Escrowed native SOL is wrapped into the wrapped SOL account of the solver
+            transfer_sol_signed(
+                &native_vault_account.to_account_info(),
+                &solver_token_account.to_account_info(),
+                to_u64(order.amount())?,
+            )?;
+            sync_wrapped_sol(
+                solver_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        }
     } else {
         let token_vault_account = ctx
             .accounts
@@ -151,45 +270,96 @@ pub fn resolve_fill(
             .as_ref()
             .ok_or(IntentError::TokenVaultAccountIsMissing)?;
 
-        let solver_token_account = ctx
+        let mint = ctx
             .accounts
-            .solver_token_account
+            .mint
             .as_ref()
-            .ok_or(IntentError::SolverTokenAccountIsMissing)?;
-
-        transfer_spl_token_signed(
-            token_vault_account.to_account_info(),
-            solver_token_account.to_account_info(),
-            ctx.accounts.config.to_account_info(),
-            order.amount() as u64,
-            ctx.accounts.token_program.to_account_info(),
-            &ctx.accounts.config,
-        )?;
+            .ok_or(IntentError::MintAccountIsMissing)?;
+
+        if payout_token == NATIVE_ADDRESS {
+            let unwrap_token_account = ctx
+                .accounts
+                .unwrap_token_account
+                .as_ref()
+                .ok_or(IntentError::UnwrapTokenAccountIsMissing)?;
+
+            // Escrowed wrapped SOL is unwrapped by closing a temporary account to the signer,
+            // who keeps back the rent of the account and forwards the unwrapped lamports
+            transfer_spl_token_signed(
+                token_vault_account.to_account_info(),
+                unwrap_token_account.to_account_info(),
+                ctx.accounts.config.to_account_info(),
+                mint,
+                to_u64(order.amount())?,
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.config,
+            )?;
+            close_token_account_signed(
+                unwrap_token_account.to_account_info(),
+                ctx.accounts.signer.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.config,
+            )?;
+            transfer_sol(
+                &ctx.accounts.signer,
+                &ctx.accounts.solver,
+                to_u64(order.amount())?,
+                &ctx.accounts.system_program,
+            )?;
+        } else {
+            let solver_token_account = ctx
+                .accounts
+                .solver_token_account
+                .as_ref()
+                .ok_or(IntentError::SolverTokenAccountIsMissing)?;
+
+            // The vault only holds the escrowed amount, so any transfer fee is paid by the solver
+            transfer_spl_token_signed(
+                token_vault_account.to_account_info(),
+                solver_token_account.to_account_info(),
+                ctx.accounts.config.to_account_info(),
+                mint,
+                to_u64(order.amount())?,
+                ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.config,
+            )?;
+        }
     }
 
-    emit!(event::OrderClosed { id: order.id() });
+    let order_index = &mut ctx.accounts.order_index;
+    order_index.remove_order(order.id());
+    resize_account(
+        &order_index.to_account_info(),
+        OrderIndex::size(order_index.orders.len()),
+        &ctx.accounts.signer,
+        &ctx.accounts.order_creator,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit_cpi!(event::OrderClosed { id: order.id() });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(order: SwapOrder)]
 pub struct FillCtx<'info> {
-    #[account(mut)]
+    /// The solver filling the order, which must be the exclusive solver of the order until
+    /// its exclusivity deadline
+    #[account(
+        mut,
+        constraint = order.order_data().unwrap_or_default().is_solver_allowed(&signer.key(), Clock::get()?.unix_timestamp) @IntentError::OnlyExclusiveSolver
+    )]
     pub signer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 
-    #[account(mut)]
-    pub config: Box<Account<'info, Config>>,
-
-    /// CHECK: The account which receives the protocol fee for each order being filled.
-    /// This account is validated against `config.fee_handler`.
     #[account(
         mut,
-        address = config.fee_handler @ IntentError::InvalidFeeHandler
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
     )]
-    pub fee_handler: AccountInfo<'info>,
+    pub config: Box<Account<'info, Config>>,
 
     /// CHECK: The destination address where order creator wants to receive the intent
     /// order. This account is validated in instruction.
@@ -205,41 +375,60 @@ pub struct FillCtx<'info> {
     )]
     pub order_finished: Box<Account<'info, OrderFinished>>,
 
-    /// The associated token account of fee handler of the intent program
+    /// The fee vault accruing the protocol fee of native SOL fills
     #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = fee_handler
+        init_if_needed,
+        payer = signer,
+        space = VaultNative::SIZE,
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &Pubkey::from_str(&order.to_token()).unwrap().to_bytes()],
+        bump
+    )]
+    pub fee_vault_native: Option<Box<Account<'info, VaultNative>>>,
+
+    /// The fee vault accruing the protocol fee of `mint` fills, claimed by the fee handler
+    #[account(
+        init_if_needed,
+        payer = signer,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &Pubkey::from_str(&order.to_token()).unwrap().to_bytes()],
+        bump
     )]
-    pub fee_handler_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The assocaited token account derived from `destination_address`.
     #[account(
         init_if_needed,
         payer = signer,
         associated_token::mint = mint,
-        associated_token::authority = destination_address
+        associated_token::authority = destination_address,
+        associated_token::token_program = token_program
     )]
-    pub destination_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The token account of the signer
     #[account(
         mut,
         token::mint = mint,
-        token::authority = signer
+        token::authority = signer,
+        token::token_program = token_program
     )]
-    pub signer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub signer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
+    /// The mint of `to_token`, or the wrapped SOL mint when native SOL is delivered wrapped
     #[account(
-        constraint = mint.key().to_string() == order.to_token() @IntentError::MintAccountMismatch
+        mint::token_program = token_program,
+        constraint = [order.to_token(), order.payout_to_token()].contains(&mint.key().to_string()) @IntentError::MintAccountMismatch
     )]
-    pub mint: Option<Box<Account<'info, Mint>>>,
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(src_network: String, fill: OrderFill, order: SwapOrder)]
 pub struct ResolveFillCtx<'info> {
@@ -253,17 +442,15 @@ pub struct ResolveFillCtx<'info> {
     )]
     pub intent: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
     pub config: Account<'info, Config>,
 
     #[account(
         mut,
-        seeds = [
-            &order_creator.key().to_bytes(),
-            order.dst_nid().as_bytes(),
-            &order.amount().to_be_bytes(),
-            &order.to_amount().to_be_bytes()
-        ],
+        seeds = [OrderAccount::SEED_PREFIX.as_bytes(), &order.id().to_be_bytes()],
         bump = order_account.bump,
         close = order_creator
     )]
@@ -272,10 +459,18 @@ pub struct ResolveFillCtx<'info> {
     /// CHECK: The order creator account validated against `order_account.order.creator`
     #[account(
         mut,
-        constraint = order_creator.key().to_string() == order.creator()
+        constraint = order_creator.key().to_string() == order_account.order.creator() @IntentError::InvalidCreatorAccount
     )]
     pub order_creator: AccountInfo<'info>,
 
+    /// The open orders index of the order creator
+    #[account(
+        mut,
+        seeds = [OrderIndex::SEED_PREFIX.as_bytes(), &order_creator.key().to_bytes()],
+        bump = order_index.bump
+    )]
+    pub order_index: Account<'info, OrderIndex>,
+
     /// CHECK: The account of the solver to receive fund intent locked amount in source chain
     /// This account is validated against `fill.solver`.
     #[account(
@@ -289,9 +484,10 @@ pub struct ResolveFillCtx<'info> {
         init_if_needed,
         payer = signer,
         associated_token::mint = mint,
-        associated_token::authority = solver
+        associated_token::authority = solver,
+        associated_token::token_program = token_program
       )]
-    pub solver_token_account: Option<Account<'info, TokenAccount>>,
+    pub solver_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// Vault native account
     #[account(
@@ -306,17 +502,33 @@ pub struct ResolveFillCtx<'info> {
         mut,
         token::mint = mint,
         token::authority = config,
+        token::token_program = token_program,
         seeds = [VAULT_TOKEN_SEED_PREFIX.as_bytes(), &Pubkey::from_str(&order.token()).unwrap().to_bytes()],
         bump
       )]
-    pub token_vault_account: Option<Account<'info, TokenAccount>>,
+    pub token_vault_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The mint of `token`, or the wrapped SOL mint when native SOL is paid out wrapped
     #[account(
-        constraint = mint.key().to_string() == order_account.order.token() @IntentError::MintAccountMismatch
+        mint::token_program = token_program,
+        constraint = [order_account.order.token(), order_account.order.payout_token()].contains(&mint.key().to_string()) @IntentError::MintAccountMismatch
     )]
-    pub mint: Option<Account<'info, Mint>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Temporary wrapped SOL account closed within the instruction to unwrap escrowed
+    /// wrapped SOL paid out as native SOL
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [UNWRAP_SEED_PREFIX.as_bytes()],
+        bump
+    )]
+    pub unwrap_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 }
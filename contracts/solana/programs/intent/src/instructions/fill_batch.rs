@@ -0,0 +1,317 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, system_instruction},
+};
+use anchor_spl::{
+    associated_token::{
+        create_idempotent, get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    connection,
+    constants::*,
+    error::*,
+    event,
+    helpers::*,
+    state::*,
+    types::{
+        misc::HookStatus,
+        order_fill::OrderFill,
+        order_message::{MessageType, OrderMessage},
+        swap_order::SwapOrder,
+    },
+};
+
+/// Fills several cross-chain orders of the same `to_token` in one instruction. The accounts
+/// of each order are passed in the remaining accounts, see `FILL_BATCH_ORDER_ACCOUNTS`, and
+/// the protocol fee of the batch is moved to the fee vault at once. Each fill is sent back
+/// in its own FILL message.
+pub fn fill_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, FillBatchCtx<'info>>,
+    to_token: Pubkey,
+    orders: Vec<SwapOrder>,
+    solver_address: String,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.ensure_not_paused(PAUSE_FILL)?;
+
+    if ctx.remaining_accounts.len() != orders.len() * FILL_BATCH_ORDER_ACCOUNTS {
+        return Err(IntentError::InvalidBatchAccounts.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut total_fee: u64 = 0;
+    // Fills to send back to the network of each order
+    let mut fills: Vec<(String, OrderFill)> = Vec::new();
+
+    let accounts = ctx.remaining_accounts.chunks(FILL_BATCH_ORDER_ACCOUNTS);
+    for (order, accounts) in orders.into_iter().zip(accounts) {
+        let destination_address = &accounts[0];
+        let order_finished = &accounts[1];
+        let destination_token_account = &accounts[2];
+
+        // Same chain orders, hooks and SOL delivered in another form need the accounts
+        // of `fill`
+        let order_data = order.order_data().unwrap_or_default();
+        if order.to_token() != to_token.to_string()
+            || order.src_nid() == order.dst_nid()
+            || order_data.hook().is_some()
+            || order_data.payout_token(&order.to_token()) != order.to_token()
+        {
+            return Err(IntentError::OrderNotBatchable.into());
+        }
+
+        if order.dst_address() != destination_address.key().to_string() {
+            return Err(IntentError::InvalidDestinationAccount.into());
+        }
+
+        if !order_data.is_solver_allowed(&ctx.accounts.signer.key(), now) {
+            return Err(IntentError::OnlyExclusiveSolver.into());
+        }
+
+        if order_data.is_expired(now) {
+            return Err(IntentError::OrderExpired.into());
+        }
+
+        init_order_finished(
+            order_finished,
+            &order,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let fill_amount = order_data.fill_amount(order.to_amount(), now)?;
+        let (fee, to_amount) = split_protocol_fee(fill_amount, config.protocol_fee)?;
+        let to_amount = to_u64(to_amount)?;
+        total_fee = total_fee
+            .checked_add(to_u64(fee)?)
+            .ok_or(IntentError::ArithmeticOverflow)?;
+
+        if order.to_token() == NATIVE_ADDRESS {
+            transfer_sol(
+                &ctx.accounts.signer,
+                destination_address,
+                to_amount,
+                &ctx.accounts.system_program,
+            )?;
+        } else {
+            let signer_token_account = ctx
+                .accounts
+                .signer_token_account
+                .as_ref()
+                .ok_or(IntentError::SignerTokenAccountIsMissing)?;
+
+            let mint = ctx
+                .accounts
+                .mint
+                .as_ref()
+                .ok_or(IntentError::MintAccountIsMissing)?;
+
+            let expected_token_account = get_associated_token_address_with_program_id(
+                &destination_address.key(),
+                &mint.key(),
+                &ctx.accounts.token_program.key(),
+            );
+            if destination_token_account.key() != expected_token_account {
+                return Err(IntentError::InvalidBatchAccounts.into());
+            }
+
+            create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: ctx.accounts.signer.to_account_info(),
+                    associated_token: destination_token_account.to_account_info(),
+                    authority: destination_address.to_account_info(),
+                    mint: mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+
+            // The solver pays any transfer fee so that the destination receives the full amount
+            transfer_spl_token(
+                signer_token_account.to_account_info(),
+                destination_token_account.to_account_info(),
+                ctx.accounts.signer.to_account_info(),
+                mint,
+                amount_with_transfer_fee(mint, to_amount)?,
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        }
+
+        emit_cpi!(event::OrderFilled {
+            id: order.id(),
+            srcNID: order.src_nid(),
+            toAmount: fill_amount,
+            hookStatus: HookStatus::None,
+        });
+
+        let fill = OrderFill::new(order.id(), order.encode(), solver_address.clone());
+        fills.push((order.src_nid(), fill));
+    }
+
+    if to_token.to_string() == NATIVE_ADDRESS {
+        let fee_vault_native = ctx
+            .accounts
+            .fee_vault_native
+            .as_mut()
+            .ok_or(IntentError::FeeVaultAccountIsMissing)?;
+        fee_vault_native.new(ctx.bumps.fee_vault_native.unwrap());
+
+        transfer_sol(
+            &ctx.accounts.signer,
+            &fee_vault_native.to_account_info(),
+            total_fee,
+            &ctx.accounts.system_program,
+        )?;
+    } else {
+        let signer_token_account = ctx
+            .accounts
+            .signer_token_account
+            .as_ref()
+            .ok_or(IntentError::SignerTokenAccountIsMissing)?;
+
+        let fee_vault_token_account = ctx
+            .accounts
+            .fee_vault_token_account
+            .as_ref()
+            .ok_or(IntentError::FeeVaultAccountIsMissing)?;
+
+        let mint = ctx
+            .accounts
+            .mint
+            .as_ref()
+            .ok_or(IntentError::MintAccountIsMissing)?;
+
+        transfer_spl_token(
+            signer_token_account.to_account_info(),
+            fee_vault_token_account.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            mint,
+            amount_with_transfer_fee(mint, total_fee)?,
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+    }
+
+    for (network, fill) in fills {
+        let order_msg = OrderMessage::new(MessageType::FILL, fill.encode());
+        connection::send_message(
+            config,
+            network,
+            order_msg.encode(),
+            &EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `OrderFinished` account of an order passed in the remaining accounts, which
+/// can't be initialized through the account constraints
+fn init_order_finished<'info>(
+    order_finished: &AccountInfo<'info>,
+    order: &SwapOrder,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let hash = order.get_hash();
+    let (pda, bump) = Pubkey::find_program_address(&[&hash], &crate::id());
+    if order_finished.key() != pda {
+        return Err(IntentError::InvalidBatchAccounts.into());
+    }
+
+    if !order_finished.data_is_empty() {
+        return Err(IntentError::OrderAlreadyFilled.into());
+    }
+
+    // The account may already hold lamports, so it is funded, allocated and assigned
+    // separately rather than created
+    let rent = Rent::get()?.minimum_balance(OrderFinished::SIZE);
+    let lamports = order_finished.lamports();
+    if rent > lamports {
+        transfer_sol(payer, order_finished, rent - lamports, system_program)?;
+    }
+
+    let signer_seeds: &[&[u8]] = &[&hash, &[bump]];
+    invoke_signed(
+        &system_instruction::allocate(&pda, OrderFinished::SIZE as u64),
+        std::slice::from_ref(order_finished),
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(&pda, &crate::id()),
+        std::slice::from_ref(order_finished),
+        &[signer_seeds],
+    )?;
+
+    let account = OrderFinished {
+        finished: true,
+        bump,
+        payer: payer.key(),
+        finished_at: Clock::get()?.unix_timestamp,
+    };
+    account.try_serialize(&mut &mut order_finished.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(to_token: Pubkey)]
+pub struct FillBatchCtx<'info> {
+    /// The solver filling the orders
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The fee vault accruing the protocol fee of native SOL fills
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = VaultNative::SIZE,
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &to_token.to_bytes()],
+        bump
+    )]
+    pub fee_vault_native: Option<Box<Account<'info, VaultNative>>>,
+
+    /// The fee vault accruing the protocol fee of `mint` fills, claimed by the fee handler
+    #[account(
+        init_if_needed,
+        payer = signer,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [FEE_VAULT_SEED_PREFIX.as_bytes(), &to_token.to_bytes()],
+        bump
+    )]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The token account of the signer, shared by all orders of the batch
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program
+    )]
+    pub signer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() == to_token @IntentError::MintAccountMismatch
+    )]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -1,13 +1,19 @@
 pub mod cancel;
 pub mod config;
+pub mod fees;
 pub mod fill;
+pub mod fill_batch;
 pub mod query_accounts;
+pub mod reclaim;
 pub mod recv_message;
 pub mod swap;
 
 pub use cancel::*;
 pub use config::*;
+pub use fees::*;
 pub use fill::*;
+pub use fill_batch::*;
 pub use query_accounts::*;
+pub use reclaim::*;
 pub use recv_message::*;
 pub use swap::*;
@@ -1,15 +1,20 @@
 use std::str::FromStr;
 
-use anchor_lang::{prelude::*, solana_program::system_program};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{system_program, sysvar},
+};
 use anchor_spl::{
-    associated_token::{get_associated_token_address, AssociatedToken},
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
     token::Token,
 };
 
 use crate::{
-    constants::*, misc::*, order_cancel::*, order_message::*, state::*, OrderFill, SwapOrder,
+    constants::*, error::IntentError, misc::*, order_cancel::*, order_message::*, state::*, OrderFill, SwapOrder,
 };
 
+/// Returns the accounts of `recv_message` for `msg`, the mints of the SPL tokens released by
+/// a fill are passed in the remaining accounts
 pub fn query_recv_message_accounts(
     ctx: Context<QueryAccountCtx>,
     src_network: String,
@@ -18,6 +23,15 @@ pub fn query_recv_message_accounts(
 ) -> Result<QueryAccountsResponse> {
     let config = &ctx.accounts.config;
 
+    let (receipt_page_pda, _) = Pubkey::find_program_address(
+        &[
+            ReceiptPage::SEED_PREFIX.as_bytes(),
+            src_network.as_bytes(),
+            &ReceiptPage::index(conn_sn).to_be_bytes(),
+        ],
+        &crate::id(),
+    );
+
     let (receipt_pda, _) = Pubkey::find_program_address(
         &[
             Receipt::SEED_PREFIX.as_bytes(),
@@ -27,107 +41,351 @@ pub fn query_recv_message_accounts(
         &crate::id(),
     );
 
+    let (verifier_set_pda, _) =
+        Pubkey::find_program_address(&[VerifierSet::SEED_PREFIX.as_bytes()], &crate::id());
+
     let mut account_metas = vec![
         AccountMetadata::new_readonly(system_program::id(), false),
         AccountMetadata::new_readonly(config.key(), false),
-        AccountMetadata::new(receipt_pda, false),
-        AccountMetadata::new(config.key(), false),
+        AccountMetadata::new(receipt_page_pda, false),
+        AccountMetadata::new_readonly(receipt_pda, false),
+        AccountMetadata::new_readonly(verifier_set_pda, false),
+        AccountMetadata::new_readonly(sysvar::instructions::id(), false),
     ];
 
-    let order_msg = OrderMessage::try_from(&msg).unwrap();
+    let order_msg = OrderMessage::try_from(&msg)?;
     match order_msg.message_type() {
         MessageType::FILL => {
-            let fill = OrderFill::try_from(&order_msg.message()).unwrap();
-            let order = SwapOrder::try_from(&fill.order_bytes()).unwrap();
+            let fill = OrderFill::try_from(&order_msg.message())?;
+            let order = SwapOrder::try_from(&fill.order_bytes())?;
+            let solver = parse_pubkey(&fill.solver())?;
 
-            let order_creator = Pubkey::from_str(&order.creator()).unwrap();
-            let solver = Pubkey::from_str(&fill.solver()).unwrap();
+            account_metas.extend(resolve_fill_accounts(
+                config.key(),
+                &order,
+                solver,
+                ctx.remaining_accounts,
+            )?);
+        }
+        MessageType::CANCEL => {
+            let cancel = Cancel::try_from(&order_msg.message())?;
+            let order = SwapOrder::try_from(&cancel.order_bytes())?;
 
-            let (order_pda, _) = Pubkey::find_program_address(
-                &[
-                    &order_creator.to_bytes(),
-                    order.dst_nid().as_bytes(),
-                    &order.amount().to_be_bytes(),
-                    &order.to_amount().to_be_bytes(),
-                ],
-                &crate::id(),
-            );
+            let (order_finished_pda, _) =
+                Pubkey::find_program_address(&[&order.get_hash()], &crate::id());
 
-            // Order account
-            account_metas.push(AccountMetadata::new(order_pda, false));
+            // Config account
+            account_metas.push(AccountMetadata::new(config.key(), false));
 
-            // Order creator
-            account_metas.push(AccountMetadata::new(order_creator, false));
+            // Order finished account
+            account_metas.push(AccountMetadata::new(order_finished_pda, false));
 
-            // Solver account
-            account_metas.push(AccountMetadata::new(solver, false));
+            account_metas.extend(event_cpi_accounts());
+        }
+    }
 
-            if order.token() == NATIVE_ADDRESS {
-                let (vault_native_pda, _) = Pubkey::find_program_address(
-                    &[VaultNative::SEED_PREFIX.as_bytes()],
-                    &crate::id(),
-                );
+    Ok(QueryAccountsResponse {
+        accounts: account_metas
+    })
+}
 
-                account_metas.push(AccountMetadata::new(crate::id(), false));
+/// Returns the accounts of `fill` for `order`, the mints of the SPL tokens of the order are
+/// passed in the remaining accounts
+pub fn query_fill_accounts(
+    ctx: Context<QueryAccountCtx>,
+    order: SwapOrder,
+    solver: Pubkey,
+) -> Result<QueryAccountsResponse> {
+    let config = &ctx.accounts.config;
 
-                // Vault native account
-                account_metas.push(AccountMetadata::new(vault_native_pda, false));
+    let destination_address = parse_pubkey(&order.dst_address())?;
+    let (order_finished_pda, _) = Pubkey::find_program_address(&[&order.get_hash()], &crate::id());
+    let to_token = parse_pubkey(&order.to_token())?;
+    let (fee_vault_pda, _) = Pubkey::find_program_address(
+        &[FEE_VAULT_SEED_PREFIX.as_bytes(), &to_token.to_bytes()],
+        &crate::id(),
+    );
 
-                account_metas.push(AccountMetadata::new(crate::id(), false));
-                account_metas.push(AccountMetadata::new(crate::id(), false));
-            } else {
-                let token_mint_address = Pubkey::from_str(&order.token()).unwrap();
+    let mut account_metas = vec![
+        AccountMetadata::new(solver, true),
+        AccountMetadata::new_readonly(system_program::id(), false),
+        AccountMetadata::new(config.key(), false),
+        AccountMetadata::new(destination_address, false),
+        AccountMetadata::new(order_finished_pda, false),
+    ];
 
-                let (vault_token_pda, _) = Pubkey::find_program_address(
-                    &[
-                        VAULT_TOKEN_SEED_PREFIX.as_bytes(),
-                        &token_mint_address.to_bytes(),
-                    ],
-                    &crate::id(),
-                );
+    let payout_token = order.payout_to_token();
 
-                let solver_token_account =
-                    get_associated_token_address(&solver, &token_mint_address);
+    let token_program = if order.to_token() == NATIVE_ADDRESS && payout_token == NATIVE_MINT {
+        let mint = parse_pubkey(NATIVE_MINT)?;
 
-                // Solver token account
-                account_metas.push(AccountMetadata::new(solver_token_account, false));
+        // Fee vault native account
+        account_metas.push(AccountMetadata::new(fee_vault_pda, false));
 
-                // Vault native account (null)
-                account_metas.push(AccountMetadata::new(crate::id(), false));
+        // Fee vault token account (null)
+        account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
 
-                // Vault token account
-                account_metas.push(AccountMetadata::new(vault_token_pda, false));
+        // Wrapped SOL account of the destination
+        account_metas.push(AccountMetadata::new(
+            get_associated_token_address_with_program_id(&destination_address, &mint, &Token::id()),
+            false,
+        ));
 
-                // Mint account
-                account_metas.push(AccountMetadata::new(token_mint_address, false));
-            }
+        // Signer token account (null)
+        account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
+
+        // Wrapped SOL mint account
+        account_metas.push(AccountMetadata::new_readonly(mint, false));
 
-            // Token program
-            account_metas.push(AccountMetadata::new(Token::id(), false));
+        Token::id()
+    } else if order.to_token() == NATIVE_ADDRESS {
+        // Fee vault native account
+        account_metas.push(AccountMetadata::new(fee_vault_pda, false));
 
-            // Associated token program
-            account_metas.push(AccountMetadata::new(AssociatedToken::id(), false));
+        // Fee vault, destination and signer token accounts and the mint account (null)
+        for _ in 0..4 {
+            account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
         }
-        MessageType::CANCEL => {
-            let cancel = Cancel::try_from(&order_msg.message()).unwrap();
-            let order = SwapOrder::try_from(&cancel.order_bytes()).unwrap();
 
-            let (order_finished_pda, _) =
-                Pubkey::find_program_address(&[&order.get_hash()], &crate::id());
+        Token::id()
+    } else if payout_token == NATIVE_ADDRESS {
+        let mint = to_token;
 
-            // Order finished account
-            account_metas.push(AccountMetadata::new(order_finished_pda, false));
+        // Fee vault native account (null)
+        account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
+
+        // Fee vault token account, wrapped SOL is delivered natively
+        account_metas.push(AccountMetadata::new(fee_vault_pda, false));
 
-            account_metas.push(AccountMetadata::new(crate::id(), false));
+        // Destination and signer token accounts (null)
+        for _ in 0..2 {
+            account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
+        }
+
+        // Wrapped SOL mint account
+        account_metas.push(AccountMetadata::new_readonly(mint, false));
+
+        Token::id()
+    } else {
+        let mint = to_token;
+        let token_program = token_program_id(mint, ctx.remaining_accounts)?;
+
+        // Fee vault native account (null)
+        account_metas.push(AccountMetadata::new_readonly(crate::id(), false));
+
+        // Fee vault token account
+        account_metas.push(AccountMetadata::new(fee_vault_pda, false));
+
+        // Destination token account
+        account_metas.push(AccountMetadata::new(
+            get_associated_token_address_with_program_id(
+                &destination_address,
+                &mint,
+                &token_program,
+            ),
+            false,
+        ));
+
+        // Signer token account
+        account_metas.push(AccountMetadata::new(
+            get_associated_token_address_with_program_id(&solver, &mint, &token_program),
+            false,
+        ));
+
+        // Mint account
+        account_metas.push(AccountMetadata::new_readonly(mint, false));
+
+        token_program
+    };
+
+    // Token program
+    account_metas.push(AccountMetadata::new_readonly(token_program, false));
+
+    // Associated token program
+    account_metas.push(AccountMetadata::new_readonly(AssociatedToken::id(), false));
+
+    account_metas.extend(event_cpi_accounts());
+
+    // Same chain orders are resolved in the fill transaction through `invoke_resolve`,
+    // which passes the remaining accounts positionally to `resolve_fill`. The hook accounts
+    // are looked up by key, so they follow the accounts of `resolve_fill`.
+    if order.src_nid() == order.dst_nid() {
+        account_metas.extend(resolve_fill_accounts(
+            config.key(),
+            &order,
+            solver,
+            ctx.remaining_accounts,
+        )?);
+    }
+
+    // Program and accounts of the post-fill hook, which are looked up in the remaining accounts
+    if let Some(hook) = order.order_data().ok().as_ref().and_then(|data| data.hook()) {
+        account_metas.push(AccountMetadata::new_readonly(hook.program_id(), false));
+        for account in hook.accounts() {
+            if account.is_writable {
+                account_metas.push(AccountMetadata::new(account.pubkey, false));
+            } else {
+                account_metas.push(AccountMetadata::new_readonly(account.pubkey, false));
+            }
         }
     }
 
     Ok(QueryAccountsResponse {
-        accounts: account_metas
+        accounts: account_metas,
     })
 }
 
+/// Returns the accounts of `resolve_fill` following the accounts passed by `invoke_resolve`
+fn resolve_fill_accounts(
+    config: Pubkey,
+    order: &SwapOrder,
+    solver: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<AccountMetadata>> {
+    let order_creator = parse_pubkey(&order.creator())?;
+
+    let (order_pda, _) = Pubkey::find_program_address(
+        &[
+            OrderAccount::SEED_PREFIX.as_bytes(),
+            &order.id().to_be_bytes(),
+        ],
+        &crate::id(),
+    );
+
+    let (order_index_pda, _) = Pubkey::find_program_address(
+        &[
+            OrderIndex::SEED_PREFIX.as_bytes(),
+            &order_creator.to_bytes(),
+        ],
+        &crate::id(),
+    );
+
+    let mut account_metas = vec![
+        // Config account
+        AccountMetadata::new(config, false),
+        // Order account
+        AccountMetadata::new(order_pda, false),
+        // Order creator
+        AccountMetadata::new(order_creator, false),
+        // Order index of the creator
+        AccountMetadata::new(order_index_pda, false),
+        // Solver account
+        AccountMetadata::new(solver, false),
+    ];
+
+    let mut token_program = Token::id();
+    let payout_token = order.payout_token();
+
+    if order.token() == NATIVE_ADDRESS && payout_token == NATIVE_MINT {
+        let mint = parse_pubkey(NATIVE_MINT)?;
+        let (vault_native_pda, _) =
+            Pubkey::find_program_address(&[VaultNative::SEED_PREFIX.as_bytes()], &crate::id());
+
+        // Wrapped SOL account of the solver
+        account_metas.push(AccountMetadata::new(
+            get_associated_token_address_with_program_id(&solver, &mint, &token_program),
+            false,
+        ));
+
+        // Vault native account
+        account_metas.push(AccountMetadata::new(vault_native_pda, false));
+
+        // Vault token account (null)
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+
+        // Wrapped SOL mint account
+        account_metas.push(AccountMetadata::new(mint, false));
+    } else if order.token() == NATIVE_ADDRESS {
+        let (vault_native_pda, _) =
+            Pubkey::find_program_address(&[VaultNative::SEED_PREFIX.as_bytes()], &crate::id());
+
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+
+        // Vault native account
+        account_metas.push(AccountMetadata::new(vault_native_pda, false));
+
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+    } else {
+        let token_mint_address = parse_pubkey(&order.token())?;
+        token_program = token_program_id(token_mint_address, remaining_accounts)?;
+
+        let (vault_token_pda, _) = Pubkey::find_program_address(
+            &[
+                VAULT_TOKEN_SEED_PREFIX.as_bytes(),
+                &token_mint_address.to_bytes(),
+            ],
+            &crate::id(),
+        );
+
+        let solver_token_account = if payout_token == NATIVE_ADDRESS {
+            crate::id()
+        } else {
+            get_associated_token_address_with_program_id(&solver, &token_mint_address, &token_program)
+        };
+
+        // Solver token account (null when wrapped SOL is paid out natively)
+        account_metas.push(AccountMetadata::new(solver_token_account, false));
+
+        // Vault native account (null)
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+
+        // Vault token account
+        account_metas.push(AccountMetadata::new(vault_token_pda, false));
+
+        // Mint account
+        account_metas.push(AccountMetadata::new(token_mint_address, false));
+    }
+
+    // Token program
+    account_metas.push(AccountMetadata::new(token_program, false));
+
+    // Associated token program
+    account_metas.push(AccountMetadata::new(AssociatedToken::id(), false));
+
+    // Temporary account unwrapping escrowed wrapped SOL
+    if order.token() == NATIVE_MINT && payout_token == NATIVE_ADDRESS {
+        let (unwrap_pda, _) =
+            Pubkey::find_program_address(&[UNWRAP_SEED_PREFIX.as_bytes()], &crate::id());
+        account_metas.push(AccountMetadata::new(unwrap_pda, false));
+    } else {
+        account_metas.push(AccountMetadata::new(crate::id(), false));
+    }
+
+    account_metas.extend(event_cpi_accounts());
+
+    Ok(account_metas)
+}
+
+fn parse_pubkey(address: &str) -> Result<Pubkey> {
+    Pubkey::from_str(address).map_err(|_| IntentError::InvalidPubkey.into())
+}
+
+/// Returns the event authority and the program account of the `#[event_cpi]` contexts
+fn event_cpi_accounts() -> Vec<AccountMetadata> {
+    let (event_authority_pda, _) =
+        Pubkey::find_program_address(&[b"__event_authority"], &crate::id());
+
+    vec![
+        AccountMetadata::new_readonly(event_authority_pda, false),
+        AccountMetadata::new_readonly(crate::id(), false),
+    ]
+}
+
+/// Returns the token program owning the mint, read from the mint account passed in the
+/// remaining accounts so that Token-2022 mints resolve their own token accounts
+fn token_program_id(mint: Pubkey, remaining_accounts: &[AccountInfo]) -> Result<Pubkey> {
+    remaining_accounts
+        .iter()
+        .find(|account| account.key() == mint)
+        .map(|account| *account.owner)
+        .ok_or(IntentError::MintAccountIsMissing.into())
+}
+
 #[derive(Accounts)]
 pub struct QueryAccountCtx<'info> {
+    #[account(
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
     pub config: Account<'info, Config>,
 }
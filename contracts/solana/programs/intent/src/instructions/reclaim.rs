@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::*, state::*, types::swap_order::SwapOrder};
+
+/// Ensures the finished account of `order` is no longer needed, the account itself is closed
+/// by the `CloseOrderFinishedCtx` constraints
+pub fn close_order_finished(ctx: Context<CloseOrderFinishedCtx>, order: &SwapOrder) -> Result<()> {
+    let deadline = order.order_data().unwrap_or_default().deadline();
+
+    let order_finished = &ctx.accounts.order_finished;
+    if !order_finished.is_closable(deadline, Clock::get()?.unix_timestamp) {
+        return Err(IntentError::OrderFinishedNotClosable.into());
+    }
+
+    Ok(())
+}
+
+pub fn migrate_receipt(ctx: Context<MigrateReceiptCtx>, conn_sn: u128) -> Result<()> {
+    ctx.accounts
+        .receipt_page
+        .set_received(conn_sn, ctx.bumps.receipt_page);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order: SwapOrder)]
+pub struct CloseOrderFinishedCtx<'info> {
+    /// The finished account of the order, which is closed once the order can no longer
+    /// be filled
+    #[account(
+        mut,
+        seeds = [&order.get_hash()],
+        bump = order_finished.bump,
+        close = payer
+    )]
+    pub order_finished: Account<'info, OrderFinished>,
+
+    /// CHECK: The account that paid the rent of the finished account, which receives the
+    /// rent back. This account is checked against the `order_finished.payer`.
+    #[account(
+        mut,
+        address = order_finished.payer @IntentError::InvalidRentPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u128)]
+pub struct CloseCancelRequestCtx<'info> {
+    /// CHECK: The account of the order, which is closed when the refund or a fill of the
+    /// order is resolved. A settled order can not be expired again, so its cancel request is
+    /// no longer needed.
+    #[account(
+        seeds = [OrderAccount::SEED_PREFIX.as_bytes(), &order_id.to_be_bytes()],
+        bump,
+        constraint = order_account.data_is_empty() @IntentError::CancelRequestNotClosable
+    )]
+    pub order_account: UncheckedAccount<'info>,
+
+    /// The cancel request sent by `expire`, which is closed
+    #[account(
+        mut,
+        seeds = [CancelRequest::SEED_PREFIX.as_bytes(), &order_id.to_be_bytes()],
+        bump = cancel_request.bump,
+        close = payer
+    )]
+    pub cancel_request: Account<'info, CancelRequest>,
+
+    /// CHECK: The account that paid the rent of the cancel request, which receives the rent
+    /// back. This account is checked against the `cancel_request.payer`.
+    #[account(
+        mut,
+        address = cancel_request.payer @IntentError::InvalidRentPayer
+    )]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(src_network: String, conn_sn: u128)]
+pub struct MigrateReceiptCtx<'info> {
+    /// The relayer that paid the rent of the receipt, which receives the rent back
+    #[account(
+        mut,
+        address = config.relayer @IntentError::OnlyRelayer
+    )]
+    pub relayer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The configuration account, which stores important settings for the program.
+    #[account(
+        seeds = [Config::SEED_PREFIX.as_bytes()],
+        bump = config.bump,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The receipt page of `conn_sn`, which takes over the replay protection of the receipt
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = ReceiptPage::SIZE,
+        seeds = [
+            ReceiptPage::SEED_PREFIX.as_bytes(),
+            src_network.as_bytes(),
+            &ReceiptPage::index(conn_sn).to_be_bytes()
+        ],
+        bump
+    )]
+    pub receipt_page: Account<'info, ReceiptPage>,
+
+    /// The per message receipt being migrated, which is closed
+    #[account(
+        mut,
+        seeds = [Receipt::SEED_PREFIX.as_bytes(), src_network.as_bytes(), &conn_sn.to_be_bytes()],
+        bump = receipt.bump,
+        close = relayer
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
@@ -1,10 +1,11 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::{instruction::Instruction, program::invoke_signed},
+    solana_program::{instruction::Instruction, program::invoke_signed, sysvar},
 };
 
 use crate::{
     connection,
+    constants::*,
     error::IntentError,
     helpers,
     misc::*,
@@ -20,9 +21,29 @@ use crate::{
 pub fn recv_message<'info>(
     ctx: Context<'_, '_, '_, 'info, RecvMessageCtx<'info>>,
     src_network: String,
+    conn_sn: u128,
     msg: Vec<u8>,
 ) -> Result<()> {
-    connection::recv_message(&mut ctx.accounts.receipt, ctx.bumps.receipt)?;
+    ctx.accounts.config.ensure_not_paused(PAUSE_RECV)?;
+
+    match VerifierSet::try_load(&ctx.accounts.verifier_set)? {
+        Some(verifier_set) if verifier_set.is_enabled() => connection::verify_attestations(
+            &verifier_set,
+            &ctx.accounts.instructions_sysvar,
+            &ctx.accounts.config.network_id,
+            &src_network,
+            conn_sn,
+            &msg,
+        )?,
+        _ => ctx.accounts.config.ensure_relayer(ctx.accounts.signer.key())?,
+    }
+
+    connection::recv_message(
+        &mut ctx.accounts.receipt_page,
+        ctx.bumps.receipt_page,
+        &ctx.accounts.legacy_receipt,
+        conn_sn,
+    )?;
 
     let order_msg = OrderMessage::try_from(&msg)?;
     match order_msg.message_type() {
@@ -57,6 +78,7 @@ pub fn recv_message<'info>(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn invoke_resolve<'info>(
     src_network: String,
     resolve: Resolve,
@@ -126,22 +148,50 @@ pub fn invoke_resolve<'info>(
 #[derive(Accounts)]
 #[instruction(src_network: String, conn_sn: u128)]
 pub struct RecvMessageCtx<'info> {
-    #[account(
-        mut,
-        address = config.admin @IntentError::OnlyRelayer
-    )]
+    /// The relayer of the message, which must be `config.relayer` unless the verifier set
+    /// is enabled
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 
+    #[account(
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch
+    )]
     pub config: Account<'info, Config>,
 
+    /// The receipt page of `conn_sn`, which protects against replayed messages
     #[account(
         init_if_needed,
         payer = signer,
-        space = Receipt::SIZE,
+        space = ReceiptPage::SIZE,
+        seeds = [
+            ReceiptPage::SEED_PREFIX.as_bytes(),
+            src_network.as_bytes(),
+            &ReceiptPage::index(conn_sn).to_be_bytes()
+        ],
+        bump
+    )]
+    pub receipt_page: Account<'info, ReceiptPage>,
+
+    /// CHECK: The legacy receipt of `conn_sn`, which must not exist. A message received
+    /// before the receipt pages keeps its receipt until `migrate_receipt` moves it into its
+    /// page.
+    #[account(
         seeds = [Receipt::SEED_PREFIX.as_bytes(), src_network.as_bytes(), &conn_sn.to_be_bytes()],
         bump
     )]
-    pub receipt: Account<'info, Receipt>,
+    pub legacy_receipt: UncheckedAccount<'info>,
+
+    /// CHECK: The verifier set account, which may not be initialized yet. It is loaded in
+    /// the instruction.
+    #[account(
+        seeds = [VerifierSet::SEED_PREFIX.as_bytes()],
+        bump
+    )]
+    pub verifier_set: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar holding the ed25519 precompile instructions
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 }
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::*, error::IntentError, event, helpers, state::*, types::swap_order::SwapOrder,
@@ -10,6 +10,17 @@ use crate::{
 pub fn swap_order(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
     let mut order = order;
 
+    ctx.accounts.config.ensure_not_paused(PAUSE_SWAP)?;
+
+    let order_data = order.order_data()?;
+    if order_data.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(IntentError::OrderExpired.into());
+    }
+
+    if let Some(auction) = order_data.auction() {
+        auction.validate(order.to_amount())?;
+    }
+
     order.set_emitter(crate::id().to_string());
     order.set_src_nid(ctx.accounts.config.network_id.clone());
     order.set_id(ctx.accounts.config.increment_deposit_id());
@@ -18,6 +29,16 @@ pub fn swap_order(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
         .order_account
         .new(&order, ctx.bumps.order_account);
 
+    let order_index = &mut ctx.accounts.order_index;
+    order_index.add_order(order.id(), ctx.bumps.order_index);
+    helpers::resize_account(
+        &order_index.to_account_info(),
+        OrderIndex::size(order_index.orders.len()),
+        &ctx.accounts.signer,
+        &ctx.accounts.signer,
+        &ctx.accounts.system_program,
+    )?;
+
     // Escrows amount from user
     if order.token() == NATIVE_ADDRESS {
         if ctx.accounts.token_vault_account.is_some() {
@@ -33,7 +54,7 @@ pub fn swap_order(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
         helpers::transfer_sol(
             &ctx.accounts.signer,
             &native_vault_account.to_account_info(),
-            order.amount() as u64,
+            helpers::to_u64(order.amount())?,
             &ctx.accounts.system_program,
         )?;
     } else {
@@ -49,16 +70,26 @@ pub fn swap_order(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
             .as_ref()
             .ok_or(IntentError::TokenVaultAccountIsMissing)?;
 
+        let mint = ctx
+            .accounts
+            .mint
+            .as_ref()
+            .ok_or(IntentError::MintAccountIsMissing)?;
+
+        // The creator pays any transfer fee so that the vault escrows the full order amount
+        let amount = helpers::amount_with_transfer_fee(mint, helpers::to_u64(order.amount())?)?;
+
         helpers::transfer_spl_token(
             user_token_account.to_account_info(),
             token_vault_account.to_account_info(),
             ctx.accounts.signer.to_account_info(),
-            order.amount() as u64,
+            mint,
+            amount,
             ctx.accounts.token_program.to_account_info(),
         )?;
     }
 
-    emit!(event::SwapIntent {
+    emit_cpi!(event::SwapIntent {
         id: order.id(),
         emitter: order.emitter(),
         srcNID: order.src_nid(),
@@ -75,6 +106,7 @@ pub fn swap_order(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(order: SwapOrder)]
 pub struct SwapCtx<'info> {
@@ -86,23 +118,33 @@ pub struct SwapCtx<'info> {
 
     pub system_program: Program<'info, System>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = config.is_current_version() @IntentError::ConfigVersionMismatch,
+        constraint = OrderAccount::fits(&order, &config.network_id) @IntentError::OrderTooLarge
+    )]
     pub config: Box<Account<'info, Config>>,
 
+    /// The account storing the order, sized from the order. The config constraints ensure
+    /// the order does not exceed `MAX_ORDER_SIZE` before the account is created.
     #[account(
         init,
         payer = signer,
-        space = OrderAccount::SIZE,
-        seeds = [
-            &signer.key().to_bytes(),
-            order.dst_nid().as_bytes(),
-            &order.amount().to_be_bytes(),
-            &order.to_amount().to_be_bytes()
-        ],
+        space = OrderAccount::size(&order, &config.network_id),
+        seeds = [OrderAccount::SEED_PREFIX.as_bytes(), &config.next_deposit_id().to_be_bytes()],
         bump
     )]
     pub order_account: Box<Account<'info, OrderAccount>>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = OrderIndex::SIZE,
+        seeds = [OrderIndex::SEED_PREFIX.as_bytes(), &signer.key().to_bytes()],
+        bump
+    )]
+    pub order_index: Box<Account<'info, OrderIndex>>,
+
     #[account(
         mut,
         seeds = [VaultNative::SEED_PREFIX.as_bytes()],
@@ -115,19 +157,25 @@ pub struct SwapCtx<'info> {
         payer = signer,
         token::mint = mint,
         token::authority = config,
+        token::token_program = token_program,
         seeds = [VAULT_TOKEN_SEED_PREFIX.as_bytes(), &Pubkey::from_str(&order.token()).unwrap().to_bytes()],
         bump
     )]
-    pub token_vault_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub token_vault_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         mut,
         token::mint = mint,
-        token::authority = signer
+        token::authority = signer,
+        token::token_program = token_program
     )]
-    pub signer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub signer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key().to_string() == order.token() @IntentError::MintAccountMismatch
+    )]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -27,8 +27,20 @@ pub mod intent {
         instructions::initialize(ctx, fee_handler, network_id)
     }
 
-    pub fn set_admin(ctx: Context<SetAdminCtx>, account: Pubkey) -> Result<()> {
-        instructions::set_admin(ctx, account)
+    pub fn migrate_config(ctx: Context<MigrateConfigCtx>) -> Result<()> {
+        instructions::migrate_config(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdminCtx>, account: Pubkey) -> Result<()> {
+        instructions::propose_admin(ctx, account)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdminCtx>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    pub fn set_relayer(ctx: Context<SetRelayerCtx>, relayer: Pubkey) -> Result<()> {
+        instructions::set_relayer(ctx, relayer)
     }
 
     pub fn set_protocol_fee(ctx: Context<SetFeeCtx>, fee: u64) -> Result<()> {
@@ -39,6 +51,30 @@ pub mod intent {
         instructions::set_fee_handler(ctx, fee_handler)
     }
 
+    pub fn set_guardian(ctx: Context<SetGuardianCtx>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
+
+    pub fn pause(ctx: Context<PauseCtx>, flags: u8) -> Result<()> {
+        instructions::pause(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<UnpauseCtx>, flags: u8) -> Result<()> {
+        instructions::unpause(ctx, flags)
+    }
+
+    pub fn set_verifier_set(
+        ctx: Context<SetVerifierSetCtx>,
+        verifiers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_verifier_set(ctx, verifiers, threshold)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFeesCtx>, mint: Pubkey) -> Result<()> {
+        instructions::claim_fees(ctx, mint)
+    }
+
     pub fn swap(ctx: Context<SwapCtx>, order: SwapOrder) -> Result<()> {
         instructions::swap_order(ctx, order)
     }
@@ -51,6 +87,15 @@ pub mod intent {
         instructions::order_fill(ctx, order, solver_address)
     }
 
+    pub fn fill_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillBatchCtx<'info>>,
+        to_token: Pubkey,
+        orders: Vec<SwapOrder>,
+        solver_address: String,
+    ) -> Result<()> {
+        instructions::fill_batch(ctx, to_token, orders, solver_address)
+    }
+
     #[allow(unused_variables)]
     pub fn cancel<'info>(
         ctx: Context<'_, '_, '_, 'info, CancelCtx<'info>>,
@@ -60,13 +105,20 @@ pub mod intent {
     }
 
     #[allow(unused_variables)]
+    pub fn expire<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireCtx<'info>>,
+        order: SwapOrder,
+    ) -> Result<()> {
+        instructions::expire_order(ctx)
+    }
+
     pub fn recv_message<'info>(
         ctx: Context<'_, '_, '_, 'info, RecvMessageCtx<'info>>,
         src_network: String,
         conn_sn: u128,
         msg: Vec<u8>,
     ) -> Result<()> {
-        instructions::recv_message(ctx, src_network, msg)
+        instructions::recv_message(ctx, src_network, conn_sn, msg)
     }
 
     #[allow(unused_variables)]
@@ -79,22 +131,48 @@ pub mod intent {
         instructions::resolve_fill(ctx, src_network, fill)
     }
 
-    pub fn resolve_cancel<'info>(
+    pub fn resolve_cancel(
         ctx: Context<ResolveCancelCtx>,
         src_network: String,
         cancel: types::order_cancel::Cancel,
         order: SwapOrder,
     ) -> Result<()> {
         instructions::resolve_cancel(
+            ctx.accounts.signer.key(),
             src_network,
             cancel,
             &order,
             &mut ctx.accounts.config,
             &mut ctx.accounts.order_finished,
             ctx.bumps.order_finished,
+            &helpers::EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
         )
     }
 
+    #[allow(unused_variables)]
+    pub fn migrate_receipt(
+        ctx: Context<MigrateReceiptCtx>,
+        src_network: String,
+        conn_sn: u128,
+    ) -> Result<()> {
+        instructions::migrate_receipt(ctx, conn_sn)
+    }
+
+    pub fn close_order_finished(
+        ctx: Context<CloseOrderFinishedCtx>,
+        order: SwapOrder,
+    ) -> Result<()> {
+        instructions::close_order_finished(ctx, &order)
+    }
+
+    #[allow(unused_variables)]
+    pub fn close_cancel_request(
+        ctx: Context<CloseCancelRequestCtx>,
+        order_id: u128,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     pub fn query_recv_message_accounts(
         ctx: Context<QueryAccountCtx>,
         src_network: String,
@@ -103,4 +181,16 @@ pub mod intent {
     ) -> Result<QueryAccountsResponse> {
         instructions::query_recv_message_accounts(ctx, src_network, conn_sn, msg)
     }
+
+    pub fn query_accrued_fees(ctx: Context<QueryAccruedFeesCtx>, mint: Pubkey) -> Result<u64> {
+        instructions::query_accrued_fees(ctx, mint)
+    }
+
+    pub fn query_fill_accounts(
+        ctx: Context<QueryAccountCtx>,
+        order: SwapOrder,
+        solver: Pubkey,
+    ) -> Result<QueryAccountsResponse> {
+        instructions::query_fill_accounts(ctx, order, solver)
+    }
 }
@@ -1,11 +1,9 @@
-use anchor_lang::prelude::*;
+// Accounts are initialized in place by `new(&mut self, ..)` once anchor has allocated them
+#![allow(clippy::new_ret_no_self)]
 
-use crate::{constants::*, error::*, types::swap_order::SwapOrder};
+use anchor_lang::{prelude::*, Discriminator};
 
-#[account(zero_copy)]
-pub struct RandomStruct {
-    id: u128,
-}
+use crate::{constants::*, error::*, types::swap_order::SwapOrder};
 
 #[account]
 pub struct VaultNative {
@@ -31,12 +29,50 @@ pub struct Config {
     pub deposit_id: u128,
     pub conn_sn: u128,
     pub bump: u8,
+    /// Key allowed to pause the program alongside the admin, it cannot unpause
+    pub guardian: Pubkey,
+    /// Bitmask of the paused instructions, see the `PAUSE_*` constants
+    pub paused: u8,
+    /// Key allowed to deliver messages to `recv_message` while the verifier set is disabled
+    pub relayer: Pubkey,
+    /// Admin proposed by `propose_admin`, which takes over once it calls `accept_admin`
+    pub pending_admin: Pubkey,
+    /// Layout version of the account. New settings are appended to the account and filled
+    /// in by `migrate_config`, which bumps the version to `CONFIG_VERSION`.
+    pub version: u8,
+}
+
+/// Layout of the `Config` account before it was versioned
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigV0 {
+    pub admin: Pubkey,
+    pub fee_handler: Pubkey,
+    pub network_id: String,
+    pub protocol_fee: u64,
+    pub deposit_id: u128,
+    pub conn_sn: u128,
+    pub bump: u8,
 }
 
 impl Config {
     pub const SEED_PREFIX: &'static str = "config";
 
-    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 32 + 32 + 32 + 8 + 16 + 16 + 16 + 1;
+    pub fn size(network_id: &str) -> usize {
+        ACCOUNT_DISCRIMINATOR_SIZE
+            + 32
+            + 32
+            + 4
+            + network_id.len()
+            + 8
+            + 16
+            + 16
+            + 1
+            + 32
+            + 1
+            + 32
+            + 32
+            + 1
+    }
 
     pub fn new(&mut self, admin: Pubkey, fee_handler: Pubkey, network_id: String, bump: u8) {
         self.admin = admin;
@@ -46,6 +82,52 @@ impl Config {
         self.protocol_fee = 0;
         self.deposit_id = 0;
         self.conn_sn = 0;
+        self.guardian = Pubkey::default();
+        self.paused = 0;
+        self.relayer = admin;
+        self.pending_admin = Pubkey::default();
+        self.version = CONFIG_VERSION;
+    }
+
+    /// Reads the config from the data of an account of any earlier layout version and fills
+    /// in the defaults of the settings added since
+    pub fn migrate(data: &[u8]) -> Result<Self> {
+        if let Ok(mut config) = Self::try_deserialize(&mut &data[..]) {
+            if config.version >= CONFIG_VERSION {
+                return Err(IntentError::ConfigAlreadyMigrated.into());
+            }
+
+            config.version = CONFIG_VERSION;
+            return Ok(config);
+        }
+
+        if data.len() < ACCOUNT_DISCRIMINATOR_SIZE
+            || data[..ACCOUNT_DISCRIMINATOR_SIZE] != Self::DISCRIMINATOR
+        {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        let legacy = ConfigV0::deserialize(&mut &data[ACCOUNT_DISCRIMINATOR_SIZE..])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+
+        Ok(Self {
+            admin: legacy.admin,
+            fee_handler: legacy.fee_handler,
+            network_id: legacy.network_id,
+            protocol_fee: legacy.protocol_fee,
+            deposit_id: legacy.deposit_id,
+            conn_sn: legacy.conn_sn,
+            bump: legacy.bump,
+            guardian: Pubkey::default(),
+            paused: 0,
+            relayer: legacy.admin,
+            pending_admin: Pubkey::default(),
+            version: CONFIG_VERSION,
+        })
+    }
+
+    pub fn is_current_version(&self) -> bool {
+        self.version == CONFIG_VERSION
     }
 
     pub fn ensure_admin(&self, signer: Pubkey) -> Result<()> {
@@ -57,21 +139,82 @@ impl Config {
 
     pub fn ensure_fee_handler(&self, signer: Pubkey) -> Result<()> {
         if self.fee_handler != signer {
-            return Err(IntentError::OnlyAdmin.into());
+            return Err(IntentError::OnlyFeeHandler.into());
+        }
+        Ok(())
+    }
+
+    pub fn ensure_relayer(&self, signer: Pubkey) -> Result<()> {
+        if self.relayer != signer {
+            return Err(IntentError::OnlyRelayer.into());
         }
         Ok(())
     }
 
-    pub fn set_admin(&mut self, account: Pubkey) {
-        self.admin = account
+    pub fn propose_admin(&mut self, account: Pubkey) {
+        self.pending_admin = account
+    }
+
+    /// Hands over the admin role to the pending admin, returning the previous admin
+    pub fn accept_admin(&mut self) -> Pubkey {
+        let previous = self.admin;
+        self.admin = self.pending_admin;
+        self.pending_admin = Pubkey::default();
+        previous
+    }
+
+    pub fn set_relayer(&mut self, relayer: Pubkey) {
+        self.relayer = relayer
     }
 
     pub fn set_fee_handler(&mut self, fee_handler: Pubkey) {
         self.fee_handler = fee_handler
     }
 
-    pub fn set_protocol_fee(&mut self, fee: u64) {
-        self.protocol_fee = fee
+    pub fn set_guardian(&mut self, guardian: Pubkey) {
+        self.guardian = guardian
+    }
+
+    pub fn can_pause(&self, signer: Pubkey) -> bool {
+        signer == self.admin || (self.guardian != Pubkey::default() && signer == self.guardian)
+    }
+
+    pub fn pause(&mut self, flags: u8) -> Result<()> {
+        if flags == 0 || flags & !PAUSE_ALL != 0 {
+            return Err(IntentError::InvalidPauseFlags.into());
+        }
+
+        self.paused |= flags;
+        Ok(())
+    }
+
+    pub fn unpause(&mut self, flags: u8) -> Result<()> {
+        if flags == 0 || flags & !PAUSE_ALL != 0 {
+            return Err(IntentError::InvalidPauseFlags.into());
+        }
+
+        self.paused &= !flags;
+        Ok(())
+    }
+
+    pub fn ensure_not_paused(&self, flag: u8) -> Result<()> {
+        if self.paused & flag != 0 {
+            return Err(IntentError::Paused.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_protocol_fee(&mut self, fee: u64) -> Result<()> {
+        if fee as u128 > FEE_DENOMINATOR {
+            return Err(IntentError::InvalidProtocolFee.into());
+        }
+
+        self.protocol_fee = fee;
+        Ok(())
+    }
+
+    pub fn next_deposit_id(&self) -> u128 {
+        self.deposit_id + 1
     }
 
     pub fn increment_deposit_id(&mut self) -> u128 {
@@ -92,7 +235,23 @@ pub struct OrderAccount {
 }
 
 impl OrderAccount {
-    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 400 + 1;
+    pub const SEED_PREFIX: &'static str = "order";
+
+    /// Size of the account storing `order`, once `swap` sets its emitter and source network
+    pub fn size(order: &SwapOrder, network_id: &str) -> usize {
+        ACCOUNT_DISCRIMINATOR_SIZE + Self::order_size(order, network_id) + 1
+    }
+
+    /// Size of `order` as stored by `swap`, which sets its emitter and source network
+    pub fn order_size(order: &SwapOrder, network_id: &str) -> usize {
+        order.size() - order.emitter().len() - order.src_nid().len()
+            + crate::id().to_string().len()
+            + network_id.len()
+    }
+
+    pub fn fits(order: &SwapOrder, network_id: &str) -> bool {
+        Self::order_size(order, network_id) <= MAX_ORDER_SIZE
+    }
 
     pub fn new(&mut self, order: &SwapOrder, bump: u8) {
         self.order = order.to_owned();
@@ -100,21 +259,180 @@ impl OrderAccount {
     }
 }
 
+/// Ids of the open orders of a creator, so that clients can list them without scanning
+/// all program accounts. The account is resized as orders are created and closed.
+#[account]
+pub struct OrderIndex {
+    pub orders: Vec<u128>,
+    pub bump: u8,
+}
+
+impl OrderIndex {
+    pub const SEED_PREFIX: &'static str = "order_index";
+
+    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 4 + 1;
+
+    pub fn size(orders: usize) -> usize {
+        Self::SIZE + orders * 16
+    }
+
+    pub fn add_order(&mut self, id: u128, bump: u8) {
+        self.orders.push(id);
+        self.bump = bump
+    }
+
+    pub fn remove_order(&mut self, id: u128) {
+        self.orders.retain(|order| *order != id)
+    }
+}
+
 #[account]
 pub struct OrderFinished {
     pub finished: bool,
     pub bump: u8,
+    /// Account that paid the rent, which receives it back once the account is closed
+    pub payer: Pubkey,
+    /// Unix timestamp of the fill or the cancel that finished the order
+    pub finished_at: i64,
 }
 
 impl OrderFinished {
-    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 1;
+    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 1 + 32 + 8;
 
-    pub fn new(&mut self, bump: u8) {
+    pub fn new(&mut self, payer: Pubkey, bump: u8, now: i64) {
         self.finished = true;
+        self.bump = bump;
+        self.payer = payer;
+        self.finished_at = now
+    }
+
+    /// The account rejects a second fill or refund of the order until the message settling
+    /// it reaches the source network, which closes the order so that a later fill or refund
+    /// can no longer release the escrow. That message is sent when the order is finished and
+    /// delivered within `MAX_MESSAGE_DELIVERY_DELAY`, and orders with a deadline can't be
+    /// filled after it, so the retention runs from the later of the two. A solver filling
+    /// an order without a deadline after its account is closed is not paid on the source
+    /// network.
+    pub fn is_closable(&self, deadline: u64, now: i64) -> bool {
+        let deadline = i64::try_from(deadline).unwrap_or(i64::MAX);
+        now > self
+            .finished_at
+            .max(deadline)
+            .saturating_add(ORDER_FINISHED_RETENTION)
+    }
+}
+
+/// Marks an order whose cancel was sent by `expire`, so that an expired cross chain order
+/// sends a single cancel message. The account is closed by `close_cancel_request` once the
+/// order is settled.
+#[account]
+pub struct CancelRequest {
+    pub requested: bool,
+    pub bump: u8,
+    /// Account that paid the rent, which receives it back once the account is closed
+    pub payer: Pubkey,
+}
+
+impl CancelRequest {
+    pub const SEED_PREFIX: &'static str = "cancel_request";
+
+    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1 + 1 + 32;
+
+    pub fn new(&mut self, payer: Pubkey, bump: u8) {
+        self.requested = true;
+        self.bump = bump;
+        self.payer = payer
+    }
+}
+
+/// Keys attesting the messages delivered to `recv_message`. While the threshold is zero the
+/// messages are accepted from the relayer only.
+#[account]
+pub struct VerifierSet {
+    pub verifiers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl VerifierSet {
+    pub const SEED_PREFIX: &'static str = "verifier_set";
+
+    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 4 + 32 * MAX_VERIFIERS + 1 + 1;
+
+    pub fn set(&mut self, verifiers: Vec<Pubkey>, threshold: u8, bump: u8) -> Result<()> {
+        if verifiers.len() > MAX_VERIFIERS || threshold as usize > verifiers.len() {
+            return Err(IntentError::InvalidVerifierSet.into());
+        }
+
+        let mut unique = verifiers.clone();
+        unique.sort();
+        unique.dedup();
+        if unique.len() != verifiers.len() {
+            return Err(IntentError::InvalidVerifierSet.into());
+        }
+
+        self.verifiers = verifiers;
+        self.threshold = threshold;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// Loads the verifier set from its PDA, `None` if the set has never been configured
+    pub fn try_load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.owner != &crate::id() || account.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = account.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0
+    }
+}
+
+/// Replay protection of the messages delivered from a network, one bit per connection
+/// sequence number. Each page covers `ReceiptPage::MESSAGES` sequence numbers so the rent
+/// is shared by all the messages of the page.
+#[account]
+pub struct ReceiptPage {
+    pub bitmap: [u8; 128],
+    pub bump: u8,
+}
+
+impl ReceiptPage {
+    pub const SEED_PREFIX: &'static str = "receipt_page";
+
+    pub const MESSAGES: u128 = 128 * 8;
+
+    pub const SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 128 + 1;
+
+    /// Index of the page holding the receipt of `conn_sn`
+    pub fn index(conn_sn: u128) -> u128 {
+        conn_sn / Self::MESSAGES
+    }
+
+    fn position(conn_sn: u128) -> (usize, u8) {
+        let bit = (conn_sn % Self::MESSAGES) as usize;
+        (bit / 8, 1 << (bit % 8))
+    }
+
+    pub fn is_received(&self, conn_sn: u128) -> bool {
+        let (byte, mask) = Self::position(conn_sn);
+        self.bitmap[byte] & mask != 0
+    }
+
+    pub fn set_received(&mut self, conn_sn: u128, bump: u8) {
+        let (byte, mask) = Self::position(conn_sn);
+        self.bitmap[byte] |= mask;
         self.bump = bump
     }
 }
 
+/// Per message receipt used before the receipt pages, which can be migrated into its page
+/// through `migrate_receipt` to reclaim the rent
 #[account]
 pub struct Receipt {
     pub received: bool,
@@ -131,3 +449,109 @@ impl Receipt {
         self.bump = bump
     }
 }
+
+#[test]
+fn test_receipt_page() {
+    let mut page = ReceiptPage {
+        bitmap: [0; 128],
+        bump: 0,
+    };
+
+    assert_eq!(ReceiptPage::index(1023), 0);
+    assert_eq!(ReceiptPage::index(1024), 1);
+
+    page.set_received(1025, 255);
+    assert!(page.is_received(1025));
+    assert!(page.is_received(1));
+    assert!(!page.is_received(1024));
+    assert!(!page.is_received(1026));
+}
+
+#[test]
+fn test_order_finished_is_closable() {
+    let finished = OrderFinished {
+        finished: true,
+        bump: 255,
+        payer: Pubkey::new_unique(),
+        finished_at: 50,
+    };
+
+    assert!(!finished.is_closable(100, 100 + ORDER_FINISHED_RETENTION));
+    assert!(finished.is_closable(100, 101 + ORDER_FINISHED_RETENTION));
+    assert!(!finished.is_closable(u64::MAX, i64::MAX));
+
+    // Orders without a deadline are closable once the retention after the fill has passed
+    assert!(!finished.is_closable(0, 50 + ORDER_FINISHED_RETENTION));
+    assert!(finished.is_closable(0, 51 + ORDER_FINISHED_RETENTION));
+
+    // A cancel resolved after the deadline keeps the account from the cancel onwards
+    assert!(!finished.is_closable(20, 50 + ORDER_FINISHED_RETENTION));
+}
+
+#[test]
+fn test_config_migrate() {
+    let admin = Pubkey::new_unique();
+    let legacy = ConfigV0 {
+        admin,
+        fee_handler: Pubkey::new_unique(),
+        network_id: "solana".to_string(),
+        protocol_fee: 100,
+        deposit_id: 7,
+        conn_sn: 3,
+        bump: 254,
+    };
+
+    // Unversioned accounts were allocated with a fixed size larger than their data
+    let mut data = Config::DISCRIMINATOR.to_vec();
+    data.extend(legacy.try_to_vec().unwrap());
+    data.resize(161, 0);
+
+    let config = Config::migrate(&data).unwrap();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.relayer, admin);
+    assert_eq!(config.network_id, "solana");
+    assert_eq!(config.deposit_id, 7);
+    assert_eq!(config.conn_sn, 3);
+    assert_eq!(config.bump, 254);
+    assert!(config.is_current_version());
+
+    let mut migrated = vec![];
+    config.try_serialize(&mut migrated).unwrap();
+    assert_eq!(migrated.len(), Config::size("solana"));
+
+    let res = Config::migrate(&migrated).err().unwrap();
+    assert_eq!(res, IntentError::ConfigAlreadyMigrated.into());
+}
+
+#[test]
+fn test_order_account_size() {
+    let data = vec![0xab; 300];
+    let order = SwapOrder::new(
+        1,
+        "emitter".to_string(),
+        "src".to_string(),
+        "0x38.bsc".to_string(),
+        crate::id().to_string(),
+        "archway1qvyqlhsq9g3mx2u5uehxtnf4y2qjd7k2q6ex9s2j0d3c6l5y0q3qz3ds9y".to_string(),
+        NATIVE_ADDRESS.to_string(),
+        100,
+        "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA".to_string(),
+        100,
+        data,
+    );
+
+    let mut stored = order.clone();
+    stored.set_emitter(crate::id().to_string());
+    stored.set_src_nid("solana".to_string());
+
+    assert_eq!(order.size(), order.try_to_vec().unwrap().len());
+    assert_eq!(
+        OrderAccount::order_size(&order, "solana"),
+        stored.try_to_vec().unwrap().len()
+    );
+    assert!(OrderAccount::fits(&order, "solana"));
+
+    let mut large = order.clone();
+    large.set_data(vec![0; MAX_ORDER_SIZE]);
+    assert!(!OrderAccount::fits(&large, "solana"));
+}
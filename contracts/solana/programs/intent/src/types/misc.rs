@@ -1,4 +1,4 @@
-use anchor_lang::prelude::*;
+use anchor_lang::prelude::{borsh, *};
 
 use super::{order_cancel::Cancel, order_fill::OrderFill, swap_order::SwapOrder};
 
@@ -13,6 +13,17 @@ pub enum Resolve {
     Cancel(Cancel)
 }
 
+/// Outcome of the post-fill hook of an order, reported in the `OrderFilled` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum HookStatus {
+    /// The order has no hook
+    None,
+    /// The hook instruction was invoked
+    Executed,
+    /// The hook allows failure and was left out by the solver
+    Skipped,
+}
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct ResolveFillArgs {
     pub src_network: String,
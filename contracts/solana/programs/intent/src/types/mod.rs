@@ -1,5 +1,6 @@
 pub mod misc;
 pub mod order_cancel;
+pub mod order_data;
 pub mod order_fill;
 pub mod order_message;
 pub mod swap_order;
@@ -7,6 +8,7 @@ pub mod swap_order;
 use crate::IntentError;
 use anchor_lang::{prelude::borsh, AnchorDeserialize, AnchorSerialize};
 pub use misc::*;
+pub use order_data::*;
 pub use order_fill::*;
 use rlp::{Decodable, Encodable};
 pub use swap_order::*;
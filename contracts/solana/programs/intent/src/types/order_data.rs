@@ -0,0 +1,603 @@
+use anchor_lang::prelude::{borsh, Pubkey};
+
+use super::*;
+use crate::constants::{NATIVE_ADDRESS, NATIVE_MINT};
+
+/// Optional order settings carried RLP encoded in `SwapOrder.data`, so that they are covered
+/// by the order hash on every chain. An empty `data` means no settings are applied.
+#[derive(Debug, Default, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct OrderData {
+    /// Unix timestamp after which the order can no longer be filled, zero for no deadline
+    deadline: u64,
+    /// Instruction invoked once the fill has delivered `to_token` to the destination
+    hook: Option<OrderHook>,
+    /// Decaying price of `to_token`, with `SwapOrder.to_amount` as the minimum it decays to
+    auction: Option<DutchAuction>,
+    /// Solver the order is reserved for until the exclusivity deadline
+    exclusivity: Option<Exclusivity>,
+    /// Form in which SOL is paid out on Solana, following the token address when unset
+    sol_delivery: Option<SolDelivery>,
+}
+
+impl OrderData {
+    pub fn new(deadline: u64) -> Self {
+        Self {
+            deadline,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_hook(mut self, hook: OrderHook) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    pub fn with_auction(mut self, auction: DutchAuction) -> Self {
+        self.auction = Some(auction);
+        self
+    }
+
+    pub fn with_exclusivity(mut self, exclusivity: Exclusivity) -> Self {
+        self.exclusivity = Some(exclusivity);
+        self
+    }
+
+    pub fn with_sol_delivery(mut self, sol_delivery: SolDelivery) -> Self {
+        self.sol_delivery = Some(sol_delivery);
+        self
+    }
+
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    pub fn hook(&self) -> Option<&OrderHook> {
+        self.hook.as_ref()
+    }
+
+    pub fn auction(&self) -> Option<&DutchAuction> {
+        self.auction.as_ref()
+    }
+
+    pub fn exclusivity(&self) -> Option<&Exclusivity> {
+        self.exclusivity.as_ref()
+    }
+
+    pub fn sol_delivery(&self) -> Option<SolDelivery> {
+        self.sol_delivery
+    }
+
+    /// Returns the token `token` is paid out as, which swaps native SOL and the wrapped SOL
+    /// mint when the order asks for the other form
+    pub fn payout_token(&self, token: &str) -> String {
+        match self.sol_delivery {
+            Some(SolDelivery::Native) if token == NATIVE_MINT => NATIVE_ADDRESS.to_string(),
+            Some(SolDelivery::Wrapped) if token == NATIVE_ADDRESS => NATIVE_MINT.to_string(),
+            _ => token.to_string(),
+        }
+    }
+
+    /// Whether `solver` may fill the order at unix timestamp `now`
+    pub fn is_solver_allowed(&self, solver: &Pubkey, now: i64) -> bool {
+        match self.exclusivity() {
+            Some(exclusivity) => exclusivity.is_allowed(solver, now),
+            None => true,
+        }
+    }
+
+    /// Returns the amount of `to_token` required to fill the order at unix timestamp `now`
+    pub fn fill_amount(&self, to_amount: u128, now: i64) -> Result<u128, IntentError> {
+        match self.auction() {
+            Some(auction) => auction.current_amount(to_amount, now),
+            None => Ok(to_amount),
+        }
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.deadline != 0 && now > i64::try_from(self.deadline).unwrap_or(i64::MAX)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        rlp::encode(self).to_vec()
+    }
+}
+
+impl Encodable for OrderData {
+    fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+        let items = if self.sol_delivery.is_some() {
+            5
+        } else if self.exclusivity.is_some() {
+            4
+        } else if self.auction.is_some() {
+            3
+        } else if self.hook.is_some() {
+            2
+        } else {
+            1
+        };
+
+        stream.begin_list(items);
+        stream.append(&self.deadline());
+        if items > 1 {
+            append_optional(stream, self.hook());
+        }
+        if items > 2 {
+            append_optional(stream, self.auction());
+        }
+        if items > 3 {
+            append_optional(stream, self.exclusivity());
+        }
+        if items > 4 {
+            append_optional(stream, self.sol_delivery.as_ref());
+        }
+    }
+}
+
+impl Decodable for OrderData {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let deadline: u64 = rlp.val_at(0)?;
+        let hook = optional_at(rlp, 1)?;
+        let auction = optional_at(rlp, 2)?;
+        let exclusivity = optional_at(rlp, 3)?;
+        let sol_delivery = optional_at(rlp, 4)?;
+
+        Ok(Self {
+            deadline,
+            hook,
+            auction,
+            exclusivity,
+            sol_delivery,
+        })
+    }
+}
+
+/// Appends an optional field, as an empty item when unset so that later fields keep their index
+fn append_optional<T: Encodable>(stream: &mut rlp::RlpStream, value: Option<&T>) {
+    match value {
+        Some(value) => stream.append(value),
+        None => stream.append_empty_data(),
+    };
+}
+
+/// Decodes the optional trailing field at `index`, which is either missing or empty when unset
+fn optional_at<T: Decodable>(rlp: &rlp::Rlp, index: usize) -> Result<Option<T>, rlp::DecoderError> {
+    if index >= rlp.item_count()? {
+        return Ok(None);
+    }
+
+    let item = rlp.at(index)?;
+    if item.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(item.as_val()?))
+}
+
+/// Account passed to the hook instruction, which never carries signer privileges
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct HookAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+/// Instruction of a target program invoked after the fill, e.g. to deposit the received
+/// tokens into a lending market on behalf of the destination address
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct OrderHook {
+    program_id: Pubkey,
+    accounts: Vec<HookAccount>,
+    data: Vec<u8>,
+    /// Whether the order may settle without the hook. A failed CPI can't be caught on
+    /// Solana, so the solver skips a hook allowed to fail by leaving out its program account.
+    allow_failure: bool,
+}
+
+impl OrderHook {
+    pub fn new(
+        program_id: Pubkey,
+        accounts: Vec<HookAccount>,
+        data: Vec<u8>,
+        allow_failure: bool,
+    ) -> Self {
+        Self {
+            program_id,
+            accounts,
+            data,
+            allow_failure,
+        }
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    pub fn accounts(&self) -> &Vec<HookAccount> {
+        &self.accounts
+    }
+
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    pub fn allow_failure(&self) -> bool {
+        self.allow_failure
+    }
+}
+
+impl Encodable for OrderHook {
+    fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+        stream.begin_list(4);
+        stream.append(&self.program_id.to_bytes().to_vec());
+        stream.begin_list(self.accounts.len());
+        for account in &self.accounts {
+            stream.begin_list(2);
+            stream.append(&account.pubkey.to_bytes().to_vec());
+            stream.append(&account.is_writable);
+        }
+        stream.append(&self.data);
+        stream.append(&self.allow_failure);
+    }
+}
+
+impl Decodable for OrderHook {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let program_id = decode_pubkey(&rlp.at(0)?)?;
+        let accounts = rlp
+            .at(1)?
+            .iter()
+            .map(|account| {
+                Ok(HookAccount {
+                    pubkey: decode_pubkey(&account.at(0)?)?,
+                    is_writable: account.val_at(1)?,
+                })
+            })
+            .collect::<Result<Vec<HookAccount>, rlp::DecoderError>>()?;
+        let data: Vec<u8> = rlp.val_at(2)?;
+        let allow_failure: bool = rlp.val_at(3)?;
+
+        Ok(Self {
+            program_id,
+            accounts,
+            data,
+            allow_failure,
+        })
+    }
+}
+
+fn decode_pubkey(rlp: &rlp::Rlp) -> Result<Pubkey, rlp::DecoderError> {
+    let bytes: Vec<u8> = rlp.as_val()?;
+    Pubkey::try_from(bytes.as_slice()).map_err(|_| rlp::DecoderError::Custom("Invalid pubkey"))
+}
+
+/// Linear price decay of `to_token` from `start_amount` at `start_time` down to the order
+/// `to_amount` at `end_time`, after which the order fills at `to_amount`
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DutchAuction {
+    start_amount: u128,
+    start_time: u64,
+    end_time: u64,
+}
+
+impl DutchAuction {
+    pub fn new(start_amount: u128, start_time: u64, end_time: u64) -> Self {
+        Self {
+            start_amount,
+            start_time,
+            end_time,
+        }
+    }
+
+    pub fn start_amount(&self) -> u128 {
+        self.start_amount
+    }
+
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+
+    pub fn end_time(&self) -> u64 {
+        self.end_time
+    }
+
+    /// Checks the auction decays from above the order `to_amount` over a non empty window
+    pub fn validate(&self, min_amount: u128) -> Result<(), IntentError> {
+        if self.start_amount < min_amount || self.start_time >= self.end_time {
+            return Err(IntentError::InvalidAuction);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount of `to_token` the solver has to deliver at unix timestamp `now`
+    pub fn current_amount(&self, min_amount: u128, now: i64) -> Result<u128, IntentError> {
+        self.validate(min_amount)?;
+
+        let now = u64::try_from(now).unwrap_or_default();
+        if now <= self.start_time {
+            return Ok(self.start_amount);
+        }
+        if now >= self.end_time {
+            return Ok(min_amount);
+        }
+
+        let decay = (self.start_amount - min_amount)
+            .checked_mul((now - self.start_time) as u128)
+            .ok_or(IntentError::ArithmeticOverflow)?
+            / (self.end_time - self.start_time) as u128;
+
+        Ok(self.start_amount - decay)
+    }
+}
+
+impl Encodable for DutchAuction {
+    fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+        stream.begin_list(3);
+        stream.append(&self.start_amount);
+        stream.append(&self.start_time);
+        stream.append(&self.end_time);
+    }
+}
+
+impl Decodable for DutchAuction {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let start_amount: u128 = rlp.val_at(0)?;
+        let start_time: u64 = rlp.val_at(1)?;
+        let end_time: u64 = rlp.val_at(2)?;
+
+        Ok(Self {
+            start_amount,
+            start_time,
+            end_time,
+        })
+    }
+}
+
+/// Reserves the fill of an order for a quoted solver, after which it opens to every solver
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct Exclusivity {
+    /// Signer of the fill allowed before `deadline`
+    solver: Pubkey,
+    /// Unix timestamp until which only `solver` can fill the order
+    deadline: u64,
+}
+
+impl Exclusivity {
+    pub fn new(solver: Pubkey, deadline: u64) -> Self {
+        Self { solver, deadline }
+    }
+
+    pub fn solver(&self) -> Pubkey {
+        self.solver
+    }
+
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    pub fn is_allowed(&self, solver: &Pubkey, now: i64) -> bool {
+        self.solver == *solver || now > i64::try_from(self.deadline).unwrap_or(i64::MAX)
+    }
+}
+
+impl Encodable for Exclusivity {
+    fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+        stream.begin_list(2);
+        stream.append(&self.solver.to_bytes().to_vec());
+        stream.append(&self.deadline);
+    }
+}
+
+impl Decodable for Exclusivity {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let solver = decode_pubkey(&rlp.at(0)?)?;
+        let deadline: u64 = rlp.val_at(1)?;
+
+        Ok(Self { solver, deadline })
+    }
+}
+
+/// Form of SOL paid out to the destination on fills and to the solver, or the creator on
+/// refunds, when the order token is native SOL or the wrapped SOL mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum SolDelivery {
+    /// Lamports to the recipient address, unwrapping wrapped SOL
+    Native = 1,
+    /// Wrapped SOL to the associated token account of the recipient, wrapping native SOL
+    Wrapped = 2,
+}
+
+impl Encodable for SolDelivery {
+    fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+        stream.append(&(*self as u8));
+    }
+}
+
+impl Decodable for SolDelivery {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        match rlp.as_val::<u8>()? {
+            1 => Ok(Self::Native),
+            2 => Ok(Self::Wrapped),
+            _ => Err(rlp::DecoderError::Custom("Invalid SOL delivery")),
+        }
+    }
+}
+
+impl TryFrom<&Vec<u8>> for OrderData {
+    type Error = IntentError;
+    fn try_from(value: &Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(value as &[u8])
+    }
+}
+
+impl TryFrom<&[u8]> for OrderData {
+    type Error = IntentError;
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let rlp = rlp::Rlp::new(value);
+        Self::decode(&rlp).map_err(|_| IntentError::InvalidOrderData)
+    }
+}
+
+#[test]
+fn test_order_data_decode() {
+    let data = OrderData::new(1730000000);
+    let expected = hex::decode("c584671db480").unwrap();
+    let decoded = OrderData::try_from(&expected).unwrap();
+
+    assert_eq!(data.encode(), expected);
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn test_order_data_empty() {
+    let decoded = OrderData::try_from(&Vec::new()).unwrap();
+
+    assert_eq!(decoded, OrderData::default());
+    assert!(!decoded.is_expired(i64::MAX));
+}
+
+#[test]
+fn test_order_data_expiry() {
+    let data = OrderData::new(100);
+
+    assert!(!data.is_expired(100));
+    assert!(data.is_expired(101));
+
+    let data = OrderData::new(u64::MAX);
+    assert!(!data.is_expired(i64::MAX));
+}
+
+#[test]
+fn test_order_data_invalid() {
+    let res = OrderData::try_from(&hex::decode("00").unwrap());
+
+    assert!(matches!(res, Err(IntentError::InvalidOrderData)));
+}
+
+#[test]
+fn test_order_data_hook() {
+    let hook = OrderHook::new(
+        Pubkey::new_unique(),
+        vec![
+            HookAccount {
+                pubkey: Pubkey::new_unique(),
+                is_writable: true,
+            },
+            HookAccount {
+                pubkey: Pubkey::new_unique(),
+                is_writable: false,
+            },
+        ],
+        vec![1, 2, 3],
+        true,
+    );
+    let data = OrderData::new(1730000000).with_hook(hook.clone());
+    let decoded = OrderData::try_from(&data.encode()).unwrap();
+
+    assert_eq!(decoded, data);
+    assert_eq!(decoded.hook(), Some(&hook));
+}
+
+#[test]
+fn test_order_data_hook_invalid_program_id() {
+    let mut stream = rlp::RlpStream::new_list(2);
+    stream.append(&0u64);
+    stream.begin_list(4);
+    stream.append(&vec![1u8; 20]);
+    stream.begin_list(0);
+    stream.append(&Vec::<u8>::new());
+    stream.append(&false);
+
+    let res = OrderData::try_from(&stream.out().to_vec());
+
+    assert!(matches!(res, Err(IntentError::InvalidOrderData)));
+}
+
+#[test]
+fn test_order_data_auction() {
+    let auction = DutchAuction::new(2000, 100, 200);
+    let data = OrderData::new(0).with_auction(auction.clone());
+    let decoded = OrderData::try_from(&data.encode()).unwrap();
+
+    assert_eq!(decoded, data);
+    assert_eq!(decoded.hook(), None);
+    assert_eq!(decoded.auction(), Some(&auction));
+}
+
+#[test]
+fn test_order_data_auction_decay() {
+    let data = OrderData::new(0).with_auction(DutchAuction::new(2000, 100, 200));
+
+    assert_eq!(data.fill_amount(1000, 0).unwrap(), 2000);
+    assert_eq!(data.fill_amount(1000, 100).unwrap(), 2000);
+    assert_eq!(data.fill_amount(1000, 125).unwrap(), 1750);
+    assert_eq!(data.fill_amount(1000, 199).unwrap(), 1010);
+    assert_eq!(data.fill_amount(1000, 200).unwrap(), 1000);
+    assert_eq!(data.fill_amount(1000, 300).unwrap(), 1000);
+    assert_eq!(OrderData::new(0).fill_amount(1000, 125).unwrap(), 1000);
+}
+
+#[test]
+fn test_order_data_auction_invalid() {
+    let below_min = DutchAuction::new(999, 100, 200);
+    let empty_window = DutchAuction::new(2000, 200, 200);
+
+    assert!(matches!(
+        below_min.validate(1000),
+        Err(IntentError::InvalidAuction)
+    ));
+    assert!(matches!(
+        empty_window.validate(1000),
+        Err(IntentError::InvalidAuction)
+    ));
+}
+
+#[test]
+fn test_order_data_exclusivity() {
+    let solver = Pubkey::new_unique();
+    let data = OrderData::new(0).with_exclusivity(Exclusivity::new(solver, 100));
+    let decoded = OrderData::try_from(&data.encode()).unwrap();
+
+    assert_eq!(decoded, data);
+    assert_eq!(decoded.hook(), None);
+    assert_eq!(decoded.auction(), None);
+    assert!(decoded.is_solver_allowed(&solver, 100));
+    assert!(!decoded.is_solver_allowed(&Pubkey::new_unique(), 100));
+    assert!(decoded.is_solver_allowed(&Pubkey::new_unique(), 101));
+
+    let unbounded = OrderData::new(0).with_exclusivity(Exclusivity::new(solver, u64::MAX));
+    assert!(!unbounded.is_solver_allowed(&Pubkey::new_unique(), i64::MAX));
+}
+
+#[test]
+fn test_order_data_sol_delivery() {
+    let data = OrderData::new(0).with_sol_delivery(SolDelivery::Native);
+    let decoded = OrderData::try_from(&data.encode()).unwrap();
+
+    assert_eq!(decoded, data);
+    assert_eq!(decoded.exclusivity(), None);
+    assert_eq!(decoded.payout_token(NATIVE_MINT), NATIVE_ADDRESS);
+    assert_eq!(decoded.payout_token(NATIVE_ADDRESS), NATIVE_ADDRESS);
+
+    let data = OrderData::new(0).with_sol_delivery(SolDelivery::Wrapped);
+    assert_eq!(data.payout_token(NATIVE_ADDRESS), NATIVE_MINT);
+    assert_eq!(data.payout_token(NATIVE_MINT), NATIVE_MINT);
+    assert_eq!(OrderData::default().payout_token(NATIVE_MINT), NATIVE_MINT);
+}
+
+#[test]
+fn test_order_data_sol_delivery_invalid() {
+    let mut stream = rlp::RlpStream::new_list(5);
+    stream.append(&0u64);
+    for _ in 0..3 {
+        stream.append_empty_data();
+    }
+    stream.append(&3u8);
+
+    let res = OrderData::try_from(&stream.out().to_vec());
+
+    assert!(matches!(res, Err(IntentError::InvalidOrderData)));
+}
@@ -8,6 +8,11 @@ pub struct OrderFill {
     order_bytes: Vec<u8>,
     /// Address of the solver filling the order
     solver: String,
+    /// Amount of the escrowed `amount` settled by a partial fill, set by the networks that
+    /// fill orders partially. Fills sent by this program never carry it, the amount delivered
+    /// to the destination is reported in the `OrderFilled` event instead, and `resolve_fill`
+    /// rejects the fills carrying it as orders are released as a whole.
+    amount: Option<u128>,
 }
 
 impl OrderFill {
@@ -16,6 +21,7 @@ impl OrderFill {
             id,
             order_bytes,
             solver,
+            amount: None,
         }
     }
 
@@ -31,6 +37,10 @@ impl OrderFill {
         self.solver.clone()
     }
 
+    pub fn amount(&self) -> Option<u128> {
+        self.amount
+    }
+
     pub fn encode(&self) -> Vec<u8> {
         rlp::encode(self).to_vec()
     }
@@ -38,10 +48,13 @@ impl OrderFill {
 
 impl Encodable for OrderFill {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-        stream.begin_list(3);
+        stream.begin_list(if self.amount.is_some() { 4 } else { 3 });
         stream.append(&self.id());
         stream.append(&self.order_bytes());
         stream.append(&self.solver());
+        if let Some(amount) = self.amount {
+            stream.append(&amount);
+        }
     }
 }
 
@@ -50,11 +63,17 @@ impl Decodable for OrderFill {
         let id: u128 = rlp.val_at(0)?;
         let order_bytes: Vec<u8> = rlp.val_at(1)?;
         let solver: String = rlp.val_at(2)?;
+        let amount: Option<u128> = if rlp.item_count()? > 3 {
+            Some(rlp.val_at(3)?)
+        } else {
+            None
+        };
 
         Ok(Self {
             id,
             order_bytes,
             solver,
+            amount,
         })
     }
 }
@@ -102,3 +121,23 @@ fn test_order_fill_decode_2() {
     assert_eq!(data.encode(), expected);
     assert_eq!(decoded, data)
 }
+
+#[test]
+fn test_order_fill_partial() {
+    let order_bytes = hex::decode("cb0a6bbccfccde6be9f10ae781b9d9b00d6e63").unwrap();
+    let solver = "0x6c449988e2f33302803c93f8287dc1d8cb33848a".to_string();
+
+    let mut stream = rlp::RlpStream::new_list(4);
+    stream.append(&3u128);
+    stream.append(&order_bytes);
+    stream.append(&solver);
+    stream.append(&1750u128);
+    let encoded = stream.out().to_vec();
+
+    let decoded: OrderFill = OrderFill::try_from(&encoded).unwrap();
+
+    assert_eq!(decoded.amount(), Some(1750));
+    assert_eq!(decoded.encode(), encoded);
+    assert_eq!(OrderFill::new(3, order_bytes, solver).amount(), None);
+}
+
@@ -29,6 +29,7 @@ pub struct SwapOrder {
 }
 
 impl SwapOrder {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: u128,
         emitter: String,
@@ -117,6 +118,39 @@ impl SwapOrder {
         self.data = data
     }
 
+    /// Borsh serialized size of the order
+    pub fn size(&self) -> usize {
+        let strings = [
+            &self.emitter,
+            &self.src_nid,
+            &self.dst_nid,
+            &self.creator,
+            &self.destination_address,
+            &self.token,
+            &self.to_token,
+        ];
+
+        16 * 3 + strings.iter().map(|s| 4 + s.len()).sum::<usize>() + 4 + self.data.len()
+    }
+
+    pub fn order_data(&self) -> Result<OrderData, IntentError> {
+        OrderData::try_from(&self.data)
+    }
+
+    /// Returns the token escrowed `token` is paid out as to the solver or back to the creator
+    pub fn payout_token(&self) -> String {
+        self.order_data()
+            .unwrap_or_default()
+            .payout_token(&self.token)
+    }
+
+    /// Returns the token `to_token` is delivered as to the destination
+    pub fn payout_to_token(&self) -> String {
+        self.order_data()
+            .unwrap_or_default()
+            .payout_token(&self.to_token)
+    }
+
     pub fn get_hash(&self) -> Vec<u8> {
         keccak::hash(&self.encode()).to_bytes().to_vec()
     }
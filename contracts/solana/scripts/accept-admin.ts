@@ -0,0 +1,24 @@
+import { TxnHelpers } from "./utils/transaction";
+import { connection, wallet, intentProgram } from "./setup";
+import { IntentPda } from "./setup";
+
+let txnHelpers = new TxnHelpers(connection, wallet.payer);
+
+const acceptAdmin = async () => {
+  return await intentProgram.methods
+    .acceptAdmin()
+    .accountsStrict({
+      pendingAdmin: wallet.publicKey,
+      config: IntentPda.config().pda,
+    })
+    .signers([wallet.payer])
+    .rpc();
+};
+
+acceptAdmin()
+  .then(async (sig) => {
+    await txnHelpers.logParsedTx(sig);
+  })
+  .catch((err) => {
+    console.log("Error while accepting admin: ", err);
+  });
@@ -0,0 +1,27 @@
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+
+import { TxnHelpers } from "./utils/transaction";
+import { connection, wallet, intentProgram } from "./setup";
+import { IntentPda } from "./setup";
+
+let txnHelpers = new TxnHelpers(connection, wallet.payer);
+
+const migrateConfig = async () => {
+  return await intentProgram.methods
+    .migrateConfig()
+    .accountsStrict({
+      config: IntentPda.config().pda,
+      admin: wallet.publicKey,
+      systemProgram: SYSTEM_PROGRAM_ID,
+    })
+    .signers([wallet.payer])
+    .rpc();
+};
+
+migrateConfig()
+  .then(async (sig) => {
+    await txnHelpers.logParsedTx(sig);
+  })
+  .catch((err) => {
+    console.log("Error while migrating config: ", err);
+  });
@@ -0,0 +1,23 @@
+import { TxnHelpers } from "./utils/transaction";
+import { connection, wallet, getPauseIx } from "./setup";
+
+let args = process.argv.slice(2);
+if (args.length != 1) throw new Error("Invalid arguments");
+
+const flags = Number(args[0]);
+
+let txnHelpers = new TxnHelpers(connection, wallet.payer);
+
+const pause = async () => {
+  const pauseIx = await getPauseIx(flags, wallet.publicKey);
+  const tx = await txnHelpers.buildV0Txn([pauseIx], [wallet.payer]);
+  return await connection.sendTransaction(tx);
+};
+
+pause()
+  .then(async (sig) => {
+    await txnHelpers.logParsedTx(sig);
+  })
+  .catch((err) => {
+    console.log("Error while pausing: ", err);
+  });
@@ -11,9 +11,9 @@ const adminKey = new PublicKey(args[0]);
 
 let txnHelpers = new TxnHelpers(connection, wallet.payer);
 
-const setAdmin = async () => {
+const proposeAdmin = async () => {
   return await intentProgram.methods
-    .setAdmin(adminKey)
+    .proposeAdmin(adminKey)
     .accountsStrict({
       admin: wallet.publicKey,
       config: IntentPda.config().pda,
@@ -22,10 +22,10 @@ const setAdmin = async () => {
     .rpc();
 };
 
-setAdmin()
+proposeAdmin()
   .then(async (sig) => {
     await txnHelpers.logParsedTx(sig);
   })
   .catch((err) => {
-    console.log("Error while setting admin: ", err);
+    console.log("Error while proposing admin: ", err);
   });
@@ -1,13 +1,24 @@
 import * as anchor from "@coral-xyz/anchor";
 import os from "os";
-import { PublicKey, Connection } from "@solana/web3.js";
+import * as rlp from "rlp";
+import {
+  Connection,
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+} from "@solana/web3.js";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
-import { ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-
-import { keccakHash, loadKeypairFromFile, uint128ToArray } from "./utils";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress, NATIVE_MINT, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+
+import {
+  keccakHash,
+  loadKeypairFromFile,
+  SYSVAR_INSTRUCTIONS_ID,
+  uint128ToArray,
+} from "./utils";
 import { Intent } from "../target/types/intent";
 import intentIdl from "../target/idl/intent.json";
-import { MessageType, SwapOrder } from "./types";
+import { MessageType, OrderData, OrderHook, SwapOrder } from "./types";
 
 /** RPC PROVIDER */
 export const RPC_URL = "http://127.0.0.1:8899";
@@ -27,6 +38,15 @@ export const intentProgram: anchor.Program<Intent> = new anchor.Program(
   provider
 ) as unknown as anchor.Program<Intent>;
 
+/** PAUSE FLAGS, matching the `PAUSE_*` constants of the program */
+export const PAUSE_SWAP = 1 << 0;
+export const PAUSE_FILL = 1 << 1;
+export const PAUSE_CANCEL = 1 << 2;
+export const PAUSE_RECV = 1 << 3;
+
+/** Number of connection sequence numbers covered by a receipt page */
+export const RECEIPT_PAGE_MESSAGES = 1024;
+
 export class IntentPda {
   constructor() {}
 
@@ -39,19 +59,18 @@ export class IntentPda {
     return { bump, pda };
   }
 
-  static order(
-    creator: PublicKey,
-    dstNID: string,
-    amount: number,
-    toAmount: number
-  ) {
+  static order(depositId: number) {
     let [pda, bump] = PublicKey.findProgramAddressSync(
-      [
-        creator.toBuffer(),
-        Buffer.from(dstNID),
-        uint128ToArray(amount),
-        uint128ToArray(toAmount),
-      ],
+      [Buffer.from("order"), uint128ToArray(depositId)],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
+  static orderIndex(creator: PublicKey) {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("order_index"), creator.toBuffer()],
       intentProgram.programId
     );
 
@@ -67,6 +86,24 @@ export class IntentPda {
     return { bump, pda };
   }
 
+  static feeVault(mint: PublicKey) {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), mint.toBuffer()],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
+  static unwrap() {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap")],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
   static vaultNative() {
     let [pda, bump] = PublicKey.findProgramAddressSync(
       [Buffer.from("vault_native")],
@@ -76,6 +113,15 @@ export class IntentPda {
     return { bump, pda };
   }
 
+  static cancelRequest(depositId: number) {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cancel_request"), uint128ToArray(depositId)],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
   static orderFinished(order: SwapOrder) {
     let encoded = order.encode();
     let hash = keccakHash(Buffer.from(encoded));
@@ -88,6 +134,39 @@ export class IntentPda {
     return { pda, bump };
   }
 
+  /** Authority signing the self-CPI through which the program emits its events */
+  static eventAuthority() {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
+  static verifierSet() {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("verifier_set")],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
+  static receiptPage(srcNID: string, connSn: number) {
+    let [pda, bump] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("receipt_page"),
+        Buffer.from(srcNID),
+        uint128ToArray(Math.floor(connSn / RECEIPT_PAGE_MESSAGES)),
+      ],
+      intentProgram.programId
+    );
+
+    return { bump, pda };
+  }
+
+  /** Per message receipt used before the receipt pages */
   static receipt(srcNID: string, connSn: number) {
     let [pda, bump] = PublicKey.findProgramAddressSync(
       [Buffer.from("receipt"), Buffer.from(srcNID), uint128ToArray(connSn)],
@@ -98,6 +177,12 @@ export class IntentPda {
   }
 }
 
+/** Returns the token program owning the mint, either SPL Token or Token-2022 */
+export const getTokenProgramId = async (mint: PublicKey) => {
+  const mintInfo = await connection.getAccountInfo(mint);
+  return mintInfo ? mintInfo.owner : TOKEN_PROGRAM_ID;
+};
+
 export const getSwapIx = async (swap: any) => {
   const swapOrder = SwapOrder.from(swap);
   const creator = new PublicKey(swapOrder.creator);
@@ -106,29 +191,40 @@ export const getSwapIx = async (swap: any) => {
   let mint = null;
   let tokenVaultAccount = null;
   let signerTokenAccount = null;
+  let tokenProgram = TOKEN_PROGRAM_ID;
   if (swapOrder.token != SYSTEM_PROGRAM_ID.toString()) {
     mint = new PublicKey(swapOrder.token);
+    tokenProgram = await getTokenProgramId(mint);
     tokenVaultAccount = IntentPda.vaultToken(mint).pda;
-    signerTokenAccount = await getAssociatedTokenAddress(mint, creator);
+    signerTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      creator,
+      false,
+      tokenProgram
+    );
   }
 
+  // The order account is derived from the deposit id assigned to the order
+  const config = await intentProgram.account.config.fetch(
+    IntentPda.config().pda
+  );
+  const depositId = config.depositId.toNumber() + 1;
+
   return await intentProgram.methods
     .swap(swap)
     .accountsStrict({
       systemProgram: SYSTEM_PROGRAM_ID,
       signer: creator,
       config: IntentPda.config().pda,
-      orderAccount: IntentPda.order(
-        creator,
-        swapOrder.dstNID,
-        Number(swapOrder.amount),
-        Number(swapOrder.toAmount)
-      ).pda,
+      orderAccount: IntentPda.order(depositId).pda,
+      orderIndex: IntentPda.orderIndex(creator).pda,
       nativeVaultAccount: IntentPda.vaultNative().pda,
       tokenVaultAccount,
       signerTokenAccount,
       mint,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      tokenProgram,
+      eventAuthority: IntentPda.eventAuthority().pda,
+      program: intentProgram.programId,
     })
     .instruction();
 };
@@ -136,30 +232,50 @@ export const getSwapIx = async (swap: any) => {
 export const getFillIx = async (
   swap: any,
   solverKey: PublicKey,
-  solverAddress: string
+  solverAddress: string,
+  hook?: OrderHook
 ) => {
   const swapOrder = SwapOrder.from(swap);
   const destinationAddress = new PublicKey(swapOrder.destinationAddress);
 
-  let configPda = IntentPda.config().pda;
-  const config = await intentProgram.account.config.fetch(configPda);
+  // Protocol fees accrue in the fee vault of the token
+  const feeVault = IntentPda.feeVault(new PublicKey(swapOrder.toToken)).pda;
+  let feeVaultNative = feeVault;
+  let feeVaultToken = null;
 
   // Required token accounts to fill the order (for SPL token)
   let mint = null;
-  let feeHandlerTokenAddress = null;
   let destinationTokenAddress = null;
   let solverTokenAddress = null;
+  let tokenProgram = TOKEN_PROGRAM_ID;
+  const payoutToken = OrderData.payoutToken(swapOrder.data, swapOrder.toToken);
   if (swapOrder.toToken != SYSTEM_PROGRAM_ID.toString()) {
     mint = new PublicKey(swapOrder.toToken);
-    feeHandlerTokenAddress = await getAssociatedTokenAddress(
-      mint,
-      config.feeHandler
-    );
+    tokenProgram = await getTokenProgramId(mint);
+    feeVaultNative = null;
+    feeVaultToken = feeVault;
+    // wrapped SOL delivered natively is paid in lamports by the solver
+    if (payoutToken != SYSTEM_PROGRAM_ID.toString()) {
+      destinationTokenAddress = await getAssociatedTokenAddress(
+        mint,
+        destinationAddress,
+        false,
+        tokenProgram
+      );
+      solverTokenAddress = await getAssociatedTokenAddress(
+        mint,
+        solverKey,
+        false,
+        tokenProgram
+      );
+    }
+  } else if (payoutToken == NATIVE_MINT.toString()) {
+    // native SOL delivered wrapped to the wrapped SOL account of the destination
+    mint = NATIVE_MINT;
     destinationTokenAddress = await getAssociatedTokenAddress(
       mint,
       destinationAddress
     );
-    solverTokenAddress = await getAssociatedTokenAddress(mint, solverKey);
   }
 
   // if `srcNID` and `dstNID` of order is same then We need to prepare the remaining accounts
@@ -170,26 +286,150 @@ export const getFillIx = async (
     remainingAccounts = await getResolveFillAccounts(swapOrder, solverAddress);
   }
 
+  // Program and accounts of the post-fill hook, a hook allowing failure is skipped by
+  // leaving them out
+  if (hook) {
+    remainingAccounts.push({
+      pubkey: hook.programId,
+      isSigner: false,
+      isWritable: false,
+    });
+    hook.accounts.forEach((account) =>
+      remainingAccounts.push({
+        pubkey: account.pubkey,
+        isSigner: false,
+        isWritable: account.isWritable,
+      })
+    );
+  }
+
   return await intentProgram.methods
     .fill(swap, solverAddress.toString())
     .accountsStrict({
       systemProgram: SYSTEM_PROGRAM_ID,
       signer: solverKey,
       config: IntentPda.config().pda,
-      feeHandler: config.feeHandler,
       destinationAddress: destinationAddress,
       orderFinished: IntentPda.orderFinished(swapOrder).pda,
-      feeHandlerTokenAccount: feeHandlerTokenAddress,
+      feeVaultNative,
+      feeVaultTokenAccount: feeVaultToken,
       destinationTokenAccount: destinationTokenAddress,
       signerTokenAccount: solverTokenAddress,
       mint: mint,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: IntentPda.eventAuthority().pda,
+      program: intentProgram.programId,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+};
+
+export const getFillBatchIx = async (
+  swaps: any[],
+  solverKey: PublicKey,
+  solverAddress: string
+) => {
+  const toToken = new PublicKey(SwapOrder.from(swaps[0]).toToken);
+
+  // Protocol fees of the batch accrue in the fee vault of the token
+  const feeVault = IntentPda.feeVault(toToken).pda;
+  let feeVaultNative = feeVault;
+  let feeVaultToken = null;
+
+  // Shared token accounts to fill the orders (for SPL token)
+  let mint = null;
+  let solverTokenAddress = null;
+  let tokenProgram = TOKEN_PROGRAM_ID;
+  if (!toToken.equals(SYSTEM_PROGRAM_ID)) {
+    mint = toToken;
+    tokenProgram = await getTokenProgramId(mint);
+    feeVaultNative = null;
+    feeVaultToken = feeVault;
+    solverTokenAddress = await getAssociatedTokenAddress(
+      mint,
+      solverKey,
+      false,
+      tokenProgram
+    );
+  }
+
+  // Destination, order finished and destination token accounts of each order
+  let remainingAccounts = [];
+  for (const swap of swaps) {
+    const swapOrder = SwapOrder.from(swap);
+    const destinationAddress = new PublicKey(swapOrder.destinationAddress);
+    let destinationTokenAddress = intentProgram.programId;
+    if (mint) {
+      destinationTokenAddress = await getAssociatedTokenAddress(
+        mint,
+        destinationAddress,
+        false,
+        tokenProgram
+      );
+    }
+
+    remainingAccounts.push(
+      { pubkey: destinationAddress, isWritable: true, isSigner: false },
+      {
+        pubkey: IntentPda.orderFinished(swapOrder).pda,
+        isWritable: true,
+        isSigner: false,
+      },
+      { pubkey: destinationTokenAddress, isWritable: true, isSigner: false }
+    );
+  }
+
+  return await intentProgram.methods
+    .fillBatch(toToken, swaps, solverAddress)
+    .accountsStrict({
+      systemProgram: SYSTEM_PROGRAM_ID,
+      signer: solverKey,
+      config: IntentPda.config().pda,
+      feeVaultNative,
+      feeVaultTokenAccount: feeVaultToken,
+      signerTokenAccount: solverTokenAddress,
+      mint,
+      tokenProgram,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: IntentPda.eventAuthority().pda,
+      program: intentProgram.programId,
     })
     .remainingAccounts(remainingAccounts)
     .instruction();
 };
 
+export const getClaimFeesIx = async (mint: PublicKey, feeHandler: PublicKey) => {
+  // Required token accounts to claim the token fees (for SPL token)
+  let tokenMint = null;
+  let feeHandlerTokenAccount = null;
+  let tokenProgram = TOKEN_PROGRAM_ID;
+  if (!mint.equals(SYSTEM_PROGRAM_ID)) {
+    tokenMint = mint;
+    tokenProgram = await getTokenProgramId(mint);
+    feeHandlerTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      feeHandler,
+      false,
+      tokenProgram
+    );
+  }
+
+  return await intentProgram.methods
+    .claimFees(mint)
+    .accountsStrict({
+      feeHandler,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      config: IntentPda.config().pda,
+      feeVault: IntentPda.feeVault(mint).pda,
+      feeHandlerTokenAccount,
+      tokenMint,
+      tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .instruction();
+};
+
 export const getCancelIx = async (swap: any) => {
   const swapOrder = SwapOrder.from(swap);
   const creatorKey = new PublicKey(swapOrder.creator);
@@ -206,13 +446,60 @@ export const getCancelIx = async (swap: any) => {
       systemProgram: SYSTEM_PROGRAM_ID,
       signer: creatorKey,
       config: IntentPda.config().pda,
-      orderAccount: IntentPda.order(
-        creatorKey,
-        swapOrder.dstNID,
-        swapOrder.amount,
-        swapOrder.toAmount
-      ).pda,
+      orderAccount: IntentPda.order(swapOrder.id).pda,
       orderFinished: order_finished,
+      eventAuthority: IntentPda.eventAuthority().pda,
+      program: intentProgram.programId,
+    })
+    .instruction();
+};
+
+export const getExpireIx = async (swap: any, signer: PublicKey) => {
+  const swapOrder = SwapOrder.from(swap);
+
+  // Only send order_finished account if the swap is being done in same chain
+  let order_finished = null;
+  if (swapOrder.srcNID == swapOrder.dstNID) {
+    order_finished = IntentPda.orderFinished(swapOrder).pda;
+  }
+
+  return await intentProgram.methods
+    .expire(swap)
+    .accountsStrict({
+      systemProgram: SYSTEM_PROGRAM_ID,
+      signer,
+      config: IntentPda.config().pda,
+      orderAccount: IntentPda.order(swapOrder.id).pda,
+      cancelRequest: IntentPda.cancelRequest(swapOrder.id).pda,
+      orderFinished: order_finished,
+      eventAuthority: IntentPda.eventAuthority().pda,
+      program: intentProgram.programId,
+    })
+    .instruction();
+};
+
+export const getCloseOrderFinishedIx = async (swap: any, payer: PublicKey) => {
+  const swapOrder = SwapOrder.from(swap);
+
+  return await intentProgram.methods
+    .closeOrderFinished(swap)
+    .accountsStrict({
+      orderFinished: IntentPda.orderFinished(swapOrder).pda,
+      payer,
+    })
+    .instruction();
+};
+
+export const getCloseCancelRequestIx = async (
+  orderId: number,
+  payer: PublicKey
+) => {
+  return await intentProgram.methods
+    .closeCancelRequest(new anchor.BN(orderId))
+    .accountsStrict({
+      orderAccount: IntentPda.order(orderId).pda,
+      cancelRequest: IntentPda.cancelRequest(orderId).pda,
+      payer,
     })
     .instruction();
 };
@@ -241,11 +528,7 @@ export const getRecvMessageIx = async (
         isWritable: true,
         isSigner: false,
       },
-      {
-        pubkey: intentProgram.programId,
-        isWritable: true,
-        isSigner: false,
-      },
+      ...getEventCpiAccounts(),
     ];
   }
 
@@ -255,12 +538,97 @@ export const getRecvMessageIx = async (
       systemProgram: SYSTEM_PROGRAM_ID,
       signer: signer,
       config: IntentPda.config().pda,
-      receipt: IntentPda.receipt(srcNetwork, connSn).pda,
+      receiptPage: IntentPda.receiptPage(srcNetwork, connSn).pda,
+      legacyReceipt: IntentPda.receipt(srcNetwork, connSn).pda,
+      verifierSet: IntentPda.verifierSet().pda,
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_ID,
     })
     .remainingAccounts(remainingAccounts)
     .instruction();
 };
 
+export const getSetVerifierSetIx = async (
+  verifiers: PublicKey[],
+  threshold: number,
+  admin: PublicKey
+) => {
+  return await intentProgram.methods
+    .setVerifierSet(verifiers, threshold)
+    .accountsStrict({
+      config: IntentPda.config().pda,
+      verifierSet: IntentPda.verifierSet().pda,
+      admin,
+      systemProgram: SYSTEM_PROGRAM_ID,
+    })
+    .instruction();
+};
+
+export const getSetGuardianIx = async (
+  guardian: PublicKey,
+  admin: PublicKey
+) => {
+  return await intentProgram.methods
+    .setGuardian(guardian)
+    .accountsStrict({
+      config: IntentPda.config().pda,
+      admin,
+    })
+    .instruction();
+};
+
+export const getPauseIx = async (flags: number, authority: PublicKey) => {
+  return await intentProgram.methods
+    .pause(flags)
+    .accountsStrict({
+      config: IntentPda.config().pda,
+      authority,
+    })
+    .instruction();
+};
+
+export const getUnpauseIx = async (flags: number, admin: PublicKey) => {
+  return await intentProgram.methods
+    .unpause(flags)
+    .accountsStrict({
+      config: IntentPda.config().pda,
+      admin,
+    })
+    .instruction();
+};
+
+/**
+ * Ed25519 precompile instructions attesting a message delivered to `recv_message` of the
+ * program configured with `networkId`
+ */
+export const getAttestationIxs = (
+  networkId: string,
+  srcNetwork: string,
+  connSn: number,
+  message: Buffer,
+  verifiers: Keypair[]
+) => {
+  const attestation = Buffer.from(
+    keccakHash(
+      rlp.encode([
+        "IntentAttestation",
+        intentProgram.programId.toBuffer(),
+        networkId,
+        srcNetwork,
+        connSn,
+        message,
+      ])
+    ),
+    "hex"
+  );
+
+  return verifiers.map((verifier) =>
+    Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: verifier.secretKey,
+      message: attestation,
+    })
+  );
+};
+
 export const getResolveFillAccounts = async (
   swapOrder: SwapOrder,
   solverAddress: string
@@ -271,10 +639,28 @@ export const getResolveFillAccounts = async (
   let mint = intentProgram.programId;
   let solverTokenAddress = intentProgram.programId;
   let vaultTokenAddress = intentProgram.programId;
+  let unwrapTokenAddress = intentProgram.programId;
+  let tokenProgram = TOKEN_PROGRAM_ID;
 
+  const payoutToken = OrderData.payoutToken(swapOrder.data, swapOrder.token);
   if (swapOrder.token != SYSTEM_PROGRAM_ID.toString()) {
     mint = new PublicKey(swapOrder.token);
+    tokenProgram = await getTokenProgramId(mint);
     vaultTokenAddress = IntentPda.vaultToken(mint).pda;
+    if (payoutToken == SYSTEM_PROGRAM_ID.toString()) {
+      // escrowed wrapped SOL is unwrapped through a temporary account
+      unwrapTokenAddress = IntentPda.unwrap().pda;
+    } else {
+      solverTokenAddress = await getAssociatedTokenAddress(
+        mint,
+        solverAddressKey,
+        false,
+        tokenProgram
+      );
+    }
+  } else if (payoutToken == NATIVE_MINT.toString()) {
+    // escrowed native SOL is wrapped into the wrapped SOL account of the solver
+    mint = NATIVE_MINT;
     solverTokenAddress = await getAssociatedTokenAddress(
       mint,
       solverAddressKey
@@ -290,12 +676,7 @@ export const getResolveFillAccounts = async (
     },
     // Order account
     {
-      pubkey: IntentPda.order(
-        orderCreator,
-        swapOrder.dstNID,
-        Number(swapOrder.amount),
-        Number(swapOrder.toAmount)
-      ).pda,
+      pubkey: IntentPda.order(swapOrder.id).pda,
       isWritable: true,
       isSigner: false,
     },
@@ -305,6 +686,12 @@ export const getResolveFillAccounts = async (
       isWritable: true,
       isSigner: false,
     },
+    // Order index account of the creator
+    {
+      pubkey: IntentPda.orderIndex(orderCreator).pda,
+      isWritable: true,
+      isSigner: false,
+    },
     // Solver account
     {
       pubkey: solverAddressKey,
@@ -337,7 +724,7 @@ export const getResolveFillAccounts = async (
     },
     // Token program
     {
-      pubkey: TOKEN_PROGRAM_ID,
+      pubkey: tokenProgram,
       isWritable: true,
       isSigner: false,
     },
@@ -347,7 +734,68 @@ export const getResolveFillAccounts = async (
       isWritable: true,
       isSigner: false,
     },
+    // Unwrap token account (null unless wrapped SOL is paid out natively)
+    {
+      pubkey: unwrapTokenAddress,
+      isWritable: true,
+      isSigner: false,
+    },
+    // Event authority and program account emitting the events
+    ...getEventCpiAccounts(),
   ];
 
   return remainingAccounts;
 };
+
+/** Discriminator of the self-CPI instructions carrying the events, `EVENT_IX_TAG_LE` */
+export const EVENT_IX_TAG = Buffer.from([
+  0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d,
+]);
+
+/** Decodes the events emitted through self-CPI from the inner instructions of a transaction */
+export const getCpiEvents = async (signature: string) => {
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const accountKeys = tx.transaction.message.getAccountKeys({
+    accountKeysFromLookups: tx.meta.loadedAddresses,
+  });
+
+  let events = [];
+  for (const inner of tx.meta.innerInstructions) {
+    for (const ix of inner.instructions) {
+      const programId = accountKeys.get(ix.programIdIndex);
+      const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+      if (
+        !programId.equals(intentProgram.programId) ||
+        !data.subarray(0, 8).equals(EVENT_IX_TAG)
+      ) {
+        continue;
+      }
+
+      const event = intentProgram.coder.events.decode(
+        anchor.utils.bytes.base64.encode(data.subarray(8))
+      );
+      if (event) {
+        events.push(event);
+      }
+    }
+  }
+
+  return events;
+};
+
+/** Accounts appended by `#[event_cpi]` to the contexts emitting events through self-CPI */
+export const getEventCpiAccounts = () => [
+  {
+    pubkey: IntentPda.eventAuthority().pda,
+    isWritable: false,
+    isSigner: false,
+  },
+  {
+    pubkey: intentProgram.programId,
+    isWritable: false,
+    isSigner: false,
+  },
+];
@@ -1,4 +1,5 @@
 export * from "./order-cancel";
+export * from "./order-data";
 export * from "./order-fill";
 export * from "./swap-order";
 export * from "./order_message";
@@ -0,0 +1,138 @@
+import * as rlp from "rlp";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { NATIVE_MINT } from "@solana/spl-token";
+
+/** Form of SOL paid out on Solana, matching `SolDelivery` of the program */
+export enum SolDelivery {
+  Native = 1,
+  Wrapped = 2,
+}
+
+export interface HookAccount {
+  pubkey: PublicKey;
+  isWritable: boolean;
+}
+
+/** Instruction invoked once the fill has delivered `toToken` to the destination */
+export class OrderHook {
+  programId: PublicKey;
+  accounts: HookAccount[];
+  data: Uint8Array;
+  allowFailure: boolean;
+
+  constructor(
+    programId: PublicKey,
+    accounts: HookAccount[],
+    data: Uint8Array,
+    allowFailure: boolean
+  ) {
+    this.programId = programId;
+    this.accounts = accounts;
+    this.data = data;
+    this.allowFailure = allowFailure;
+  }
+
+  rlpInput(): rlp.Input {
+    return [
+      this.programId.toBuffer(),
+      this.accounts.map((account) => [
+        account.pubkey.toBuffer(),
+        account.isWritable ? 1 : 0,
+      ]),
+      this.data,
+      this.allowFailure ? 1 : 0,
+    ];
+  }
+}
+
+/** Price of `toToken` decaying from `startAmount` down to the order `toAmount` */
+export class DutchAuction {
+  startAmount: bigint;
+  startTime: number;
+  endTime: number;
+
+  constructor(startAmount: bigint, startTime: number, endTime: number) {
+    this.startAmount = startAmount;
+    this.startTime = startTime;
+    this.endTime = endTime;
+  }
+
+  rlpInput(): rlp.Input {
+    return [this.startAmount, this.startTime, this.endTime];
+  }
+}
+
+/** Solver allowed to fill the order before the exclusivity `deadline` */
+export class Exclusivity {
+  solver: PublicKey;
+  deadline: number;
+
+  constructor(solver: PublicKey, deadline: number) {
+    this.solver = solver;
+    this.deadline = deadline;
+  }
+
+  rlpInput(): rlp.Input {
+    return [this.solver.toBuffer(), this.deadline];
+  }
+}
+
+export class OrderData {
+  deadline: number;
+  hook?: OrderHook;
+  auction?: DutchAuction;
+  exclusivity?: Exclusivity;
+  solDelivery?: SolDelivery;
+
+  constructor(
+    deadline: number,
+    hook?: OrderHook,
+    auction?: DutchAuction,
+    exclusivity?: Exclusivity,
+    solDelivery?: SolDelivery
+  ) {
+    this.deadline = deadline;
+    this.hook = hook;
+    this.auction = auction;
+    this.exclusivity = exclusivity;
+    this.solDelivery = solDelivery;
+  }
+
+  /** Returns the token `token` is paid out as under the encoded order `data` */
+  static payoutToken(data: Uint8Array, token: string) {
+    if (data.length == 0) {
+      return token;
+    }
+
+    const fields = rlp.decode(data) as Uint8Array[];
+    const solDelivery = fields[4] && fields[4].length ? fields[4][0] : undefined;
+    const native = SystemProgram.programId.toString();
+    if (solDelivery == SolDelivery.Native && token == NATIVE_MINT.toString()) {
+      return native;
+    }
+    if (solDelivery == SolDelivery.Wrapped && token == native) {
+      return NATIVE_MINT.toString();
+    }
+    return token;
+  }
+
+  encode() {
+    let rlpInput: rlp.Input = [this.deadline];
+    // unset fields followed by set ones are encoded as empty items
+    const fields = [
+      this.hook?.rlpInput(),
+      this.auction?.rlpInput(),
+      this.exclusivity?.rlpInput(),
+      this.solDelivery,
+    ];
+    const count = fields.map((field) => !!field).lastIndexOf(true) + 1;
+    for (const field of fields.slice(0, count)) {
+      rlpInput.push(field ? field : Buffer.alloc(0));
+    }
+    return rlp.encode(rlpInput);
+  }
+
+  decode(data: Uint8Array) {
+    return rlp.decode(data);
+  }
+}
@@ -4,15 +4,27 @@ export class OrderFill {
   id: number;
   orderBytes: Uint8Array;
   solver: string;
+  // Amount of the escrowed tokens settled by a partial fill, only set by networks filling
+  // orders partially
+  amount?: bigint;
 
-  constructor(id: number, orderBytes: Uint8Array, solver: string) {
+  constructor(
+    id: number,
+    orderBytes: Uint8Array,
+    solver: string,
+    amount?: bigint
+  ) {
     this.id = id;
     this.orderBytes = orderBytes;
     this.solver = solver;
+    this.amount = amount;
   }
 
   encode() {
     let rlpInput: rlp.Input = [this.id, this.orderBytes, this.solver];
+    if (this.amount !== undefined) {
+      rlpInput.push(this.amount);
+    }
     return rlp.encode(rlpInput);
   }
 
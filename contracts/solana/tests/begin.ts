@@ -38,6 +38,26 @@ describe("Initialize", () => {
     assert.equal(config.protocolFee.toNumber(), 0);
     assert.equal(config.feeHandler.toString(), feeHandler.publicKey.toString());
     assert.equal(config.admin.toString(), wallet.publicKey.toString());
+    assert.equal(config.relayer.toString(), wallet.publicKey.toString());
+    assert.equal(config.version, 1);
+  });
+
+  it("should fail to migrate config when it is already migrated", async () => {
+    try {
+      let ix = await intentProgram.methods
+        .migrateConfig()
+        .accountsStrict({
+          config: IntentPda.config().pda,
+          admin: wallet.publicKey,
+          systemProgram: SYSTEM_PROGRAM_ID,
+        })
+        .instruction();
+
+      let tx = await txnHelpers.buildV0Txn([ix], [wallet.payer]);
+      await connection.sendTransaction(tx);
+    } catch (err) {
+      expect(err.message).to.includes("Config is already migrated");
+    }
   });
 
   it("should fail when initializing intent program two times", async () => {
@@ -143,14 +163,14 @@ describe("Initialize", () => {
     }
   });
 
-  it("should fail to set admin", async () => {
+  it("should fail to propose admin", async () => {
     let newAdmin = Keypair.generate();
     let newKeypair = Keypair.generate();
     await txnHelpers.airdrop(newKeypair.publicKey, LAMPORTS_PER_SOL);
 
     try {
       let ix = await intentProgram.methods
-        .setAdmin(newAdmin.publicKey)
+        .proposeAdmin(newAdmin.publicKey)
         .accountsStrict({
           admin: newKeypair.publicKey,
           config: IntentPda.config().pda,
@@ -164,38 +184,122 @@ describe("Initialize", () => {
     }
   });
 
-  it("should set admin", async () => {
+  it("should fail to accept admin when not proposed", async () => {
+    let newKeypair = Keypair.generate();
+    await txnHelpers.airdrop(newKeypair.publicKey, LAMPORTS_PER_SOL);
+
+    try {
+      let ix = await intentProgram.methods
+        .acceptAdmin()
+        .accountsStrict({
+          pendingAdmin: newKeypair.publicKey,
+          config: IntentPda.config().pda,
+        })
+        .instruction();
+
+      let tx = await txnHelpers.buildV0Txn([ix], [newKeypair]);
+      await connection.sendTransaction(tx);
+    } catch (err) {
+      expect(err.message).to.includes("Only pending admin");
+    }
+  });
+
+  it("should propose and accept admin", async () => {
     let newAdmin = Keypair.generate();
     await txnHelpers.airdrop(newAdmin.publicKey, LAMPORTS_PER_SOL);
 
-    let ix = await intentProgram.methods
-      .setAdmin(newAdmin.publicKey)
+    let proposeIx = await intentProgram.methods
+      .proposeAdmin(newAdmin.publicKey)
       .accountsStrict({
         admin: wallet.publicKey,
         config: IntentPda.config().pda,
       })
       .instruction();
 
-    let tx = await txnHelpers.buildV0Txn([ix], [wallet.payer]);
-    await connection.sendTransaction(tx);
+    let proposeTx = await txnHelpers.buildV0Txn([proposeIx], [wallet.payer]);
+    await connection.sendTransaction(proposeTx);
     await sleep(2);
 
+    // admin is unchanged until the proposed admin accepts
     let config = await ctx.getConfig();
+    assert.equal(config.admin.toString(), wallet.publicKey.toString());
+    assert.equal(
+      config.pendingAdmin.toString(),
+      newAdmin.publicKey.toString()
+    );
+
+    let acceptIx = await intentProgram.methods
+      .acceptAdmin()
+      .accountsStrict({
+        pendingAdmin: newAdmin.publicKey,
+        config: IntentPda.config().pda,
+      })
+      .instruction();
+
+    let acceptTx = await txnHelpers.buildV0Txn([acceptIx], [newAdmin]);
+    await connection.sendTransaction(acceptTx);
+    await sleep(2);
+
+    config = await ctx.getConfig();
     assert.equal(config.admin.toString(), newAdmin.publicKey.toString());
+    assert.equal(config.pendingAdmin.toString(), SYSTEM_PROGRAM_ID.toString());
 
-    let anotherIx = await intentProgram.methods
-      .setAdmin(wallet.publicKey)
+    // hand the admin role back to the wallet
+    let anotherProposeIx = await intentProgram.methods
+      .proposeAdmin(wallet.publicKey)
       .accountsStrict({
         admin: newAdmin.publicKey,
         config: IntentPda.config().pda,
       })
       .instruction();
+    let anotherAcceptIx = await intentProgram.methods
+      .acceptAdmin()
+      .accountsStrict({
+        pendingAdmin: wallet.publicKey,
+        config: IntentPda.config().pda,
+      })
+      .instruction();
 
-    let anotherTx = await txnHelpers.buildV0Txn([anotherIx], [newAdmin]);
+    let anotherTx = await txnHelpers.buildV0Txn(
+      [anotherProposeIx, anotherAcceptIx],
+      [wallet.payer, newAdmin]
+    );
     await connection.sendTransaction(anotherTx);
     await sleep(2);
 
     let latestConfig = await ctx.getConfig();
     assert.equal(latestConfig.admin.toString(), wallet.publicKey.toString());
   });
+
+  it("should set relayer", async () => {
+    let newRelayer = Keypair.generate();
+
+    let ix = await intentProgram.methods
+      .setRelayer(newRelayer.publicKey)
+      .accountsStrict({
+        admin: wallet.publicKey,
+        config: IntentPda.config().pda,
+      })
+      .instruction();
+
+    let tx = await txnHelpers.buildV0Txn([ix], [wallet.payer]);
+    await connection.sendTransaction(tx);
+    await sleep(2);
+
+    let config = await ctx.getConfig();
+    assert.equal(config.relayer.toString(), newRelayer.publicKey.toString());
+
+    // the wallet relays the messages in the other tests
+    let anotherIx = await intentProgram.methods
+      .setRelayer(wallet.publicKey)
+      .accountsStrict({
+        admin: wallet.publicKey,
+        config: IntentPda.config().pda,
+      })
+      .instruction();
+
+    let anotherTx = await txnHelpers.buildV0Txn([anotherIx], [wallet.payer]);
+    await connection.sendTransaction(anotherTx);
+    await sleep(2);
+  });
 });
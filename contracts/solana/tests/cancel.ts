@@ -11,8 +11,17 @@ import {
   connection,
   getSwapIx,
   getCancelIx,
+  getExpireIx,
+  getCloseCancelRequestIx,
+  getRecvMessageIx,
 } from "../scripts/setup";
-import { SwapOrder } from "../scripts/types";
+import {
+  MessageType,
+  OrderData,
+  OrderFill,
+  OrderMessage,
+  SwapOrder,
+} from "../scripts/types";
 import { sleep } from "../scripts/utils";
 import { TestContext } from "./setup";
 
@@ -148,13 +157,10 @@ describe("Cancel", async () => {
         systemProgram: SYSTEM_PROGRAM_ID,
         signer: anotherSigner.publicKey,
         config: IntentPda.config().pda,
-        orderAccount: IntentPda.order(
-          creator.publicKey,
-          swapOrder.dstNID,
-          swapOrder.amount,
-          swapOrder.toAmount
-        ).pda,
+        orderAccount: IntentPda.order(swapOrder.id).pda,
         orderFinished: IntentPda.orderFinished(swapOrder).pda,
+        eventAuthority: IntentPda.eventAuthority().pda,
+        program: intentProgram.programId,
       })
       .instruction();
     const cancelTx = await txnHelpers.buildV0Txn([cancelIx], [anotherSigner]);
@@ -164,4 +170,153 @@ describe("Cancel", async () => {
       expect(err.message).to.includes("Signer must be a swap creator");
     }
   });
+
+  it("should expire the order after deadline", async () => {
+    let creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+    let deadline = Math.floor(Date.now() / 1000) + 2;
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount,
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new OrderData(deadline).encode()),
+    };
+
+    const swapIx = await getSwapIx(swap);
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    await connection.sendTransaction(swapTx);
+    await sleep(4);
+
+    const config = await ctx.getConfig();
+    swap.id = config.depositId;
+
+    // Anyone can expire the order once the deadline has passed
+    let keeper = Keypair.generate();
+    await txnHelpers.airdrop(keeper.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const expireIx = await getExpireIx(swap, keeper.publicKey);
+    const expireTx = await txnHelpers.buildV0Txn([expireIx], [keeper]);
+    await connection.sendTransaction(expireTx);
+    await sleep(2);
+
+    // conn_sn should be increased by one
+    const afterConfig = await ctx.getConfig();
+    assert.equal(afterConfig.connSn.toNumber(), config.connSn.toNumber() + 1);
+
+    // A second expire would send another cancel message
+    const retryIx = await getExpireIx(swap, keeper.publicKey);
+    const retryTx = await txnHelpers.buildV0Txn([retryIx], [keeper]);
+    try {
+      await connection.sendTransaction(retryTx);
+    } catch (err) {
+      expect(err.message).to.includes("Order cancel was already requested");
+    }
+
+    const retryConfig = await ctx.getConfig();
+    assert.equal(retryConfig.connSn.toNumber(), afterConfig.connSn.toNumber());
+
+    // The cancel request is kept until the order is settled
+    const orderId = swap.id.toNumber();
+    const closeIx = await getCloseCancelRequestIx(orderId, keeper.publicKey);
+    try {
+      const closeTx = await txnHelpers.buildV0Txn([closeIx], [keeper]);
+      await connection.sendTransaction(closeTx);
+      assert.fail("cancel request should not be closed before the refund");
+    } catch (err) {
+      expect(err.message).to.includes(
+        "Cancel request can not be closed before the order is settled"
+      );
+    }
+
+    // The destination network refunds the creator
+    const swapOrder = SwapOrder.from(swap);
+    const refund = new OrderFill(
+      swapOrder.id,
+      swapOrder.encode(),
+      creator.publicKey.toString()
+    );
+    const orderMessage = new OrderMessage(MessageType.FILL, refund.encode());
+    const recvMessageIx = await getRecvMessageIx(
+      dstNid,
+      200,
+      swapOrder,
+      Buffer.from(orderMessage.encode()),
+      MessageType.FILL,
+      ctx.admin.publicKey,
+      creator.publicKey.toString()
+    );
+    const recvMessageTx = await txnHelpers.buildV0Txn(
+      [recvMessageIx],
+      [ctx.admin]
+    );
+    await connection.sendTransaction(recvMessageTx);
+    await sleep(2);
+
+    // The rent of the cancel request goes back to the keeper
+    const cancelRequest = IntentPda.cancelRequest(orderId).pda;
+    const rent = (await connection.getAccountInfo(cancelRequest)).lamports;
+    const keeperBalance = await connection.getBalance(keeper.publicKey);
+
+    const closeTx = await txnHelpers.buildV0Txn([closeIx], [wallet.payer]);
+    await connection.sendTransaction(closeTx);
+    await sleep(2);
+
+    assert.isNull(await connection.getAccountInfo(cancelRequest));
+    assert.equal(
+      await connection.getBalance(keeper.publicKey),
+      keeperBalance + rent
+    );
+  });
+
+  it("should fail to expire the order before deadline", async () => {
+    let creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+    let deadline = Math.floor(Date.now() / 1000) + 3600;
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount,
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new OrderData(deadline).encode()),
+    };
+
+    const swapIx = await getSwapIx(swap);
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    await connection.sendTransaction(swapTx);
+    await sleep(2);
+
+    const config = await ctx.getConfig();
+    swap.id = config.depositId;
+
+    const expireIx = await getExpireIx(swap, creator.publicKey);
+    const expireTx = await txnHelpers.buildV0Txn([expireIx], [creator]);
+    try {
+      await connection.sendTransaction(expireTx);
+    } catch (err) {
+      expect(err.message).to.includes("Order has not expired yet");
+    }
+  });
 });
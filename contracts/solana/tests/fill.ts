@@ -9,9 +9,9 @@ import {
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  createAssociatedTokenAccount,
   getAccount,
   getAssociatedTokenAddress,
+  NATIVE_MINT,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
@@ -21,13 +21,28 @@ import {
   intentProgram,
   wallet,
   connection,
+  getClaimFeesIx,
+  getCloseOrderFinishedIx,
+  getFillBatchIx,
+  getCpiEvents,
   getFillIx,
   getSwapIx,
 } from "../scripts/setup";
-import { SwapOrder } from "../scripts/types";
+import {
+  DutchAuction,
+  Exclusivity,
+  OrderData,
+  OrderHook,
+  SolDelivery,
+  SwapOrder,
+} from "../scripts/types";
 import { sleep } from "../scripts/utils";
 import { TestContext } from "./setup";
 
+const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+);
+
 describe("Fill", async () => {
   let srcNid = "solana";
   let dstNid = "icon";
@@ -53,14 +68,8 @@ describe("Fill", async () => {
     const config = await ctx.getConfig();
     const signerTokenAddress = (await ctx.mintToken(signer.publicKey, 100))
       .address;
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(mintKey);
 
-    // Program expects this token account to be already initialized
-    const feeHandlerTokenAccount = await createAssociatedTokenAccount(
-      connection,
-      signer,
-      mintKey,
-      config.feeHandler
-    );
     // Program initializes this account if not already initialized
     let destinationTokenAccount = await getAssociatedTokenAddress(
       mintKey,
@@ -104,11 +113,11 @@ describe("Fill", async () => {
     let signerAccount = await getAccount(connection, signerTokenAddress);
     assert.equal(signerAccount.amount.toString(), expectedBalance.toString());
 
-    // check fee handler balance
+    // check accrued protocol fees
     let fee =
       (Number(swapOrder.toAmount) * config.protocolFee.toNumber()) / 10_000;
-    let feeHandlerToken = await getAccount(connection, feeHandlerTokenAccount);
-    assert.equal(feeHandlerToken.amount.toString(), fee.toString());
+    let accruedFees = await ctx.getAccruedFees(mintKey);
+    assert.equal(accruedFees, accruedFeesBeforeFill + fee);
 
     // check order creator (destination address) balance
     let destinationTokenBalance = Number(swapOrder.toAmount) - fee;
@@ -133,6 +142,7 @@ describe("Fill", async () => {
     let toAmount = new anchor.BN(1000000000);
 
     const config = await ctx.getConfig();
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
 
     let swap = {
       id: new anchor.BN(1),
@@ -158,17 +168,21 @@ describe("Fill", async () => {
     await connection.sendTransaction(fillTx);
     await sleep(2);
 
-    // check signer SOL balance
+    // check signer SOL balance, the first native fill initializes the fee vault
     let expectedBalance =
-      signerBalance - (amount.toNumber() + ctx.orderFinishedAccountRent + 5000);
+      signerBalance -
+      (amount.toNumber() +
+        ctx.orderFinishedAccountRent +
+        ctx.vaultNativeAccountRent +
+        5000);
     let signerNowBalance = await connection.getBalance(signer.publicKey);
     assert.equal(signerNowBalance.toString(), expectedBalance.toString());
 
-    // check fee handler balance
+    // check accrued protocol fees
     let fee =
       (Number(swapOrder.toAmount) * config.protocolFee.toNumber()) / 10_000;
-    let feeHandlerBalance = await connection.getBalance(config.feeHandler);
-    assert.equal(feeHandlerBalance.toString(), fee.toString());
+    let accruedFees = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
+    assert.equal(accruedFees, accruedFeesBeforeFill + fee);
 
     // check order creator (destination address) balance
     let destinationExpectedBalance = Number(swapOrder.toAmount) - fee;
@@ -179,6 +193,251 @@ describe("Fill", async () => {
     );
   });
 
+  it("should emit the fill events through self-CPI", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let solverAddress = Keypair.generate();
+    let destination = Keypair.generate();
+    let toAmount = new anchor.BN(1000000000);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: signer.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    let fillIx = await getFillIx(
+      swap,
+      signer.publicKey,
+      solverAddress.publicKey.toString()
+    );
+    let fillTx = await txnHelpers.buildV0Txn([fillIx], [signer]);
+    const signature = await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    const events = await getCpiEvents(signature);
+    assert.deepEqual(
+      events.map((event) => event.name),
+      ["SendMessage", "OrderFilled"]
+    );
+    assert.equal(events[0].data.targetNetwork, srcNid);
+    assert.equal(events[1].data.id.toNumber(), 1);
+    assert.equal(events[1].data.toAmount.toString(), toAmount.toString());
+  });
+
+  it("should fill native token at the current auction amount", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let toAmount = new anchor.BN(1000000000);
+    let startAmount = 2000000000;
+
+    // the auction has not started yet, so the solver delivers the start amount
+    let now = Math.floor(Date.now() / 1000);
+    let auction = new DutchAuction(BigInt(startAmount), now + 600, now + 1200);
+
+    const config = await ctx.getConfig();
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new OrderData(0, undefined, auction).encode()),
+    };
+
+    let fillIx = await getFillIx(
+      swap,
+      signer.publicKey,
+      signer.publicKey.toString()
+    );
+    let fillTx = await txnHelpers.buildV0Txn([fillIx], [signer]);
+    await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    // check order creator (destination address) balance
+    let fee = ctx.calculateSwapFee(config.protocolFee.toNumber(), startAmount);
+    let destinationBalance = await connection.getBalance(destination.publicKey);
+    assert.equal(destinationBalance, startAmount - fee);
+  });
+
+  it("should fill native token only by the exclusive solver", async () => {
+    let solver = Keypair.generate();
+    let otherSolver = Keypair.generate();
+    await txnHelpers.airdrop(solver.publicKey, LAMPORTS_PER_SOL * 10);
+    await txnHelpers.airdrop(otherSolver.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let now = Math.floor(Date.now() / 1000);
+    let exclusivity = new Exclusivity(solver.publicKey, now + 600);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(
+        new OrderData(0, undefined, undefined, exclusivity).encode()
+      ),
+    };
+
+    // other solvers can't fill before the exclusivity deadline
+    try {
+      let fillIx = await getFillIx(
+        swap,
+        otherSolver.publicKey,
+        otherSolver.publicKey.toString()
+      );
+      let fillTx = await txnHelpers.buildV0Txn([fillIx], [otherSolver]);
+      await connection.sendTransaction(fillTx);
+    } catch (err) {
+      expect(err.message).to.includes("Order is exclusive to another solver");
+    }
+
+    let fillIx = await getFillIx(
+      swap,
+      solver.publicKey,
+      solver.publicKey.toString()
+    );
+    let fillTx = await txnHelpers.buildV0Txn([fillIx], [solver]);
+    await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    let orderFinished = await intentProgram.account.orderFinished.fetch(
+      IntentPda.orderFinished(SwapOrder.from(swap)).pda
+    );
+    assert.equal(orderFinished.finished, true);
+  });
+
+  it("should fill native token delivered as wrapped SOL", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let toAmount = new anchor.BN(1000000000);
+    const config = await ctx.getConfig();
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(
+        new OrderData(
+          0,
+          undefined,
+          undefined,
+          undefined,
+          SolDelivery.Wrapped
+        ).encode()
+      ),
+    };
+
+    let fillIx = await getFillIx(
+      swap,
+      signer.publicKey,
+      signer.publicKey.toString()
+    );
+    let fillTx = await txnHelpers.buildV0Txn([fillIx], [signer]);
+    await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    // destination should receive wrapped SOL
+    let fee = ctx.calculateSwapFee(
+      config.protocolFee.toNumber(),
+      toAmount.toNumber()
+    );
+    let destinationTokenAccount = await getAccount(
+      connection,
+      await getAssociatedTokenAddress(NATIVE_MINT, destination.publicKey)
+    );
+    assert.equal(
+      destinationTokenAccount.amount.toString(),
+      (toAmount.toNumber() - fee).toString()
+    );
+  });
+
+  it("should fill wrapped SOL delivered as native token", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let destination = Keypair.generate();
+    let toAmount = new anchor.BN(1000000000);
+    const config = await ctx.getConfig();
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(NATIVE_MINT);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: NATIVE_MINT.toString(),
+      toAmount,
+      data: Buffer.from(
+        new OrderData(
+          0,
+          undefined,
+          undefined,
+          undefined,
+          SolDelivery.Native
+        ).encode()
+      ),
+    };
+
+    let fillIx = await getFillIx(
+      swap,
+      signer.publicKey,
+      signer.publicKey.toString()
+    );
+    let fillTx = await txnHelpers.buildV0Txn([fillIx], [signer]);
+    await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    // destination should receive native SOL
+    let fee = ctx.calculateSwapFee(
+      config.protocolFee.toNumber(),
+      toAmount.toNumber()
+    );
+    let destinationBalance = await connection.getBalance(destination.publicKey);
+    assert.equal(destinationBalance, toAmount.toNumber() - fee);
+
+    // the fee accrues as wrapped SOL
+    let accruedFees = await ctx.getAccruedFees(NATIVE_MINT);
+    assert.equal(accruedFees, accruedFeesBeforeFill + fee);
+  });
+
   it("should fill SPL token and resolve", async () => {
     // creator depsoit 11.8 SOL to receive 135k SPL token
 
@@ -217,12 +476,7 @@ describe("Fill", async () => {
     const solverTokenAddress = (await ctx.mintToken(solver.publicKey, 135000))
       .address;
 
-    // fee_handler token has to be created before filling the order
-    const feeHandlerTokenAddress = (await ctx.mintToken(config.feeHandler, 10))
-      .address;
-    const feeHandlerBalanceBeforeFill = (
-      await getAccount(connection, feeHandlerTokenAddress)
-    ).amount;
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(mintKey);
 
     swap.id = config.depositId;
     const swapOrder = SwapOrder.from(swap);
@@ -256,18 +510,13 @@ describe("Fill", async () => {
     const solverTokenAccount = await getAccount(connection, solverTokenAddress);
     assert.equal(solverTokenAccount.amount.toString(), "0");
 
-    // fee handler should have received swap fees
+    // fee vault should have accrued swap fees
     const fee = ctx.calculateSwapFee(
       config.protocolFee.toNumber(),
       toAmount.toNumber()
     );
-    const feeHandlerBalance = (
-      await getAccount(connection, feeHandlerTokenAddress)
-    ).amount;
-    assert.equal(
-      feeHandlerBalance.toString(),
-      (BigInt(fee) + feeHandlerBalanceBeforeFill).toString()
-    );
+    const accruedFees = await ctx.getAccruedFees(mintKey);
+    assert.equal(accruedFees, accruedFeesBeforeFill + fee);
 
     // destination token account should receive to_amount
     const destinationTokenAddress = await getAssociatedTokenAddress(
@@ -295,13 +544,17 @@ describe("Fill", async () => {
       vaultBalanceBeforeFill - swapOrder.amount
     );
 
-    // order creator should receive order_accont rent after the account is closed
+    // order creator should receive order_accont rent after the account is closed and the
+    // rent of the order id removed from the order index
     const creatorBalanceAfterFill = await connection.getBalance(
       creator.publicKey
     );
     assert.equal(
       creatorBalanceAfterFill,
-      creatorBalanceBeforeFill + ctx.orderAccountRent
+      creatorBalanceBeforeFill +
+        (await ctx.getOrderAccountRent(swap)) +
+        ctx.orderIndexAccountRent -
+        ctx.emptyOrderIndexAccountRent
     );
   });
 
@@ -349,9 +602,7 @@ describe("Fill", async () => {
     swap.id = config.depositId;
     const swapOrder = SwapOrder.from(swap);
 
-    const feeHandlerBalanceBeforeFill = await connection.getBalance(
-      config.feeHandler
-    );
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
     const creatorBalanceBeforeFill = await connection.getBalance(
       creator.publicKey
     );
@@ -386,14 +637,9 @@ describe("Fill", async () => {
     );
     assert.equal(destinationBalance, toAmount.toNumber() - swapFee);
 
-    // check fee handler balance
-    const feeHandlerBalanceAfterFill = await connection.getBalance(
-      config.feeHandler
-    );
-    assert.equal(
-      feeHandlerBalanceAfterFill,
-      feeHandlerBalanceBeforeFill + swapFee
-    );
+    // check accrued protocol fees
+    const accruedFees = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
+    assert.equal(accruedFees, accruedFeesBeforeFill + swapFee);
 
     // check solver balance
     const solverTokenAddress = await getAssociatedTokenAddress(
@@ -416,10 +662,133 @@ describe("Fill", async () => {
     );
     assert.equal(
       creatorBalanceAfterFill,
-      creatorBalanceBeforeFill + ctx.orderAccountRent
+      creatorBalanceBeforeFill +
+        (await ctx.getOrderAccountRent(swap)) +
+        ctx.orderIndexAccountRent -
+        ctx.emptyOrderIndexAccountRent
     );
   });
 
+  it("should query fill accounts", async () => {
+    const solver = Keypair.generate();
+    const creator = Keypair.generate();
+    const destination = Keypair.generate();
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid: srcNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: mintKey.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    const response = await intentProgram.methods
+      .queryFillAccounts(swap, solver.publicKey)
+      .accountsStrict({
+        config: IntentPda.config().pda,
+      })
+      .remainingAccounts([
+        { pubkey: mintKey, isSigner: false, isWritable: false },
+      ])
+      .view();
+
+    // accounts should match the fill instruction including the resolve accounts
+    const fillIx = await getFillIx(
+      swap,
+      solver.publicKey,
+      solver.publicKey.toString()
+    );
+    assert.deepEqual(
+      response.accounts.map((account) => account.pubkey.toString()),
+      fillIx.keys.map((account) => account.pubkey.toString())
+    );
+  });
+
+  it("should query fill accounts of a same chain order with a hook", async () => {
+    const solver = Keypair.generate();
+    const creator = Keypair.generate();
+    const destination = Keypair.generate();
+
+    const hook = new OrderHook(
+      MEMO_PROGRAM_ID,
+      [],
+      Buffer.from("deposit"),
+      false
+    );
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid: srcNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new OrderData(0, hook).encode()),
+    };
+
+    const response = await intentProgram.methods
+      .queryFillAccounts(swap, solver.publicKey)
+      .accountsStrict({
+        config: IntentPda.config().pda,
+      })
+      .remainingAccounts([
+        { pubkey: mintKey, isSigner: false, isWritable: false },
+      ])
+      .view();
+
+    // the resolve accounts are consumed positionally, so they precede the hook accounts
+    const fillIx = await getFillIx(
+      swap,
+      solver.publicKey,
+      solver.publicKey.toString(),
+      hook
+    );
+    assert.deepEqual(
+      response.accounts.map((account) => account.pubkey.toString()),
+      fillIx.keys.map((account) => account.pubkey.toString())
+    );
+  });
+
+  it("should fail to query fill accounts without the mint account", async () => {
+    const solver = Keypair.generate();
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: Keypair.generate().publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: mintKey.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    try {
+      await intentProgram.methods
+        .queryFillAccounts(swap, solver.publicKey)
+        .accountsStrict({
+          config: IntentPda.config().pda,
+        })
+        .view();
+      assert.fail("query should fail without the mint account");
+    } catch (err) {
+      expect(err.message).to.includes("Mint account is missing");
+    }
+  });
+
   it("should fail when destination account doesn't match with destination address", async () => {
     const solver = Keypair.generate();
     const creator = Keypair.generate();
@@ -430,8 +799,6 @@ describe("Fill", async () => {
     let amount = new anchor.BN(1000000000);
     let toAmount = new anchor.BN(1000000000);
 
-    const config = await ctx.getConfig();
-
     let swap = {
       id: new anchor.BN(1),
       emitter: intentProgram.programId.toString(),
@@ -453,10 +820,10 @@ describe("Fill", async () => {
         systemProgram: SYSTEM_PROGRAM_ID,
         signer: solver.publicKey,
         config: IntentPda.config().pda,
-        feeHandler: config.feeHandler,
         destinationAddress: Keypair.generate().publicKey,
         orderFinished: IntentPda.orderFinished(swapOrder).pda,
-        feeHandlerTokenAccount: null,
+        feeVaultNative: IntentPda.feeVault(SYSTEM_PROGRAM_ID).pda,
+        feeVaultTokenAccount: null,
         destinationTokenAccount: null,
         signerTokenAccount: null,
         mint: null,
@@ -520,54 +887,290 @@ describe("Fill", async () => {
     } catch (err) {
       expect(err.message).to.includes("Order has been already filled");
     }
+
+    // rent of the finished account is returned to the solver once closable
+    const swapOrder = SwapOrder.from(swap);
+    const orderFinished = await ctx.getOrderFinishedAccount(swapOrder);
+    assert.equal(orderFinished.payer.toString(), solver.publicKey.toString());
+    assert.isAbove(orderFinished.finishedAt.toNumber(), 0);
+
+    // the finished account is kept until the fill message has reached the source network
+    const closeIx = await getCloseOrderFinishedIx(swap, solver.publicKey);
+    const closeTx = await txnHelpers.buildV0Txn([closeIx], [solver]);
+    try {
+      await connection.sendTransaction(closeTx);
+    } catch (err) {
+      expect(err.message).to.includes(
+        "Order finished account can not be closed yet"
+      );
+    }
   });
 
-  it("should fail when fee handler account is not valid", async () => {
+  it("should fill native token and execute the hook", async () => {
     const solver = Keypair.generate();
-    const creator = Keypair.generate();
     await txnHelpers.airdrop(solver.publicKey, LAMPORTS_PER_SOL * 10);
 
-    let solverAddress = Keypair.generate();
-    let destination = Keypair.generate();
-    let amount = new anchor.BN(1000000000);
-    let toAmount = new anchor.BN(1000000000);
+    // the memo program stands in for a lending market or staking program
+    const hook = new OrderHook(
+      MEMO_PROGRAM_ID,
+      [],
+      Buffer.from("deposit"),
+      false
+    );
 
     let swap = {
       id: new anchor.BN(1),
       emitter: intentProgram.programId.toString(),
       srcNid,
       dstNid,
-      creator: creator.publicKey.toString(),
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: Keypair.generate().publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new OrderData(0, hook).encode()),
+    };
+
+    // a required hook can not be left out
+    try {
+      const fillIx = await getFillIx(
+        swap,
+        solver.publicKey,
+        solver.publicKey.toString()
+      );
+      const fillTx = await txnHelpers.buildV0Txn([fillIx], [solver]);
+      await connection.sendTransaction(fillTx);
+    } catch (err) {
+      expect(err.message).to.includes("Hook account is missing");
+    }
+
+    const fillIx = await getFillIx(
+      swap,
+      solver.publicKey,
+      solver.publicKey.toString(),
+      hook
+    );
+    const fillTx = await txnHelpers.buildV0Txn([fillIx], [solver]);
+    const signature = await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    // hook should be invoked after the fill
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const logs = tx.meta.logMessages.join("\n");
+    expect(logs).to.includes(
+      `Program ${MEMO_PROGRAM_ID.toString()} invoke [2]`
+    );
+    expect(logs).to.includes('Memo (len 7): "deposit"');
+  });
+
+  it("should fill native token and skip the hook allowed to fail", async () => {
+    const solver = Keypair.generate();
+    await txnHelpers.airdrop(solver.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const destination = Keypair.generate();
+    const toAmount = new anchor.BN(1000000000);
+    const hook = new OrderHook(
+      Keypair.generate().publicKey,
+      [{ pubkey: Keypair.generate().publicKey, isWritable: true }],
+      Buffer.from("deposit"),
+      true
+    );
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
       destinationAddress: destination.publicKey.toString(),
       token: mintKey.toString(),
-      amount,
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new OrderData(0, hook).encode()),
+    };
+
+    const fillIx = await getFillIx(
+      swap,
+      solver.publicKey,
+      solver.publicKey.toString()
+    );
+    const fillTx = await txnHelpers.buildV0Txn([fillIx], [solver]);
+    await connection.sendTransaction(fillTx);
+    await sleep(2);
+
+    // order should settle without the hook
+    const config = await ctx.getConfig();
+    const fee = ctx.calculateSwapFee(
+      config.protocolFee.toNumber(),
+      toAmount.toNumber()
+    );
+    const destinationBalance = await connection.getBalance(
+      destination.publicKey
+    );
+    assert.equal(destinationBalance, toAmount.toNumber() - fee);
+  });
+
+  it("should fill a batch of native token orders", async () => {
+    const solver = Keypair.generate();
+    await txnHelpers.airdrop(solver.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const config = await ctx.getConfig();
+    const toAmount = new anchor.BN(1000000000);
+    const fee = ctx.calculateSwapFee(
+      config.protocolFee.toNumber(),
+      toAmount.toNumber()
+    );
+    const accruedFeesBeforeFill = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
+
+    const destinations = [Keypair.generate(), Keypair.generate()];
+    const swaps = destinations.map((destination, index) => ({
+      id: new anchor.BN(index + 1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
       toToken: SYSTEM_PROGRAM_ID.toString(),
       toAmount,
       data: Buffer.from(new Uint8Array()),
+    }));
+
+    const fillBatchIx = await getFillBatchIx(
+      swaps,
+      solver.publicKey,
+      solver.publicKey.toString()
+    );
+    const fillBatchTx = await txnHelpers.buildV0Txn([fillBatchIx], [solver]);
+    await connection.sendTransaction(fillBatchTx);
+    await sleep(2);
+
+    // each fill is sent back in its own message
+    const afterConfig = await ctx.getConfig();
+    assert.equal(
+      afterConfig.connSn.toNumber(),
+      config.connSn.toNumber() + swaps.length
+    );
+
+    // every order should be filled
+    for (let i = 0; i < swaps.length; i++) {
+      const orderFinished = await ctx.getOrderFinishedAccount(
+        SwapOrder.from(swaps[i])
+      );
+      assert.equal(orderFinished.finished, true);
+
+      const destinationBalance = await connection.getBalance(
+        destinations[i].publicKey
+      );
+      assert.equal(destinationBalance, toAmount.toNumber() - fee);
+    }
+
+    // fees of the batch accrue at once
+    const accruedFees = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
+    assert.equal(accruedFees, accruedFeesBeforeFill + fee * swaps.length);
+
+    // filled orders can't be filled again in a batch
+    try {
+      const fillBatchIx = await getFillBatchIx(
+        swaps,
+        solver.publicKey,
+        solver.publicKey.toString()
+      );
+      const fillBatchTx = await txnHelpers.buildV0Txn([fillBatchIx], [solver]);
+      await connection.sendTransaction(fillBatchTx);
+    } catch (err) {
+      expect(err.message).to.includes("Order has been already filled");
+    }
+  });
+
+  it("should fail to fill a same chain order in a batch", async () => {
+    const solver = Keypair.generate();
+    await txnHelpers.airdrop(solver.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid: srcNid,
+      creator: Keypair.generate().publicKey.toString(),
+      destinationAddress: Keypair.generate().publicKey.toString(),
+      token: mintKey.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new Uint8Array()),
     };
-    let swapOrder = SwapOrder.from(swap);
 
-    let fillIx = await intentProgram.methods
-      .fill(swap, solverAddress.toString())
+    try {
+      const fillBatchIx = await getFillBatchIx(
+        [swap],
+        solver.publicKey,
+        solver.publicKey.toString()
+      );
+      const fillBatchTx = await txnHelpers.buildV0Txn([fillBatchIx], [solver]);
+      await connection.sendTransaction(fillBatchTx);
+    } catch (err) {
+      expect(err.message).to.includes("Order can not be filled in a batch");
+    }
+  });
+
+  it("should claim accrued fees", async () => {
+    const feeHandler = Keypair.generate();
+    await txnHelpers.airdrop(feeHandler.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const setFeeHandlerIx = await intentProgram.methods
+      .setFeeHandler(feeHandler.publicKey)
       .accountsStrict({
-        systemProgram: SYSTEM_PROGRAM_ID,
-        signer: solver.publicKey,
+        admin: wallet.publicKey,
         config: IntentPda.config().pda,
-        feeHandler: Keypair.generate().publicKey,
-        destinationAddress: destination.publicKey,
-        orderFinished: IntentPda.orderFinished(swapOrder).pda,
-        feeHandlerTokenAccount: null,
-        destinationTokenAccount: null,
-        signerTokenAccount: null,
-        mint: null,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       })
       .instruction();
-    let tx = await txnHelpers.buildV0Txn([fillIx], [solver]);
+    const setFeeHandlerTx = await txnHelpers.buildV0Txn(
+      [setFeeHandlerIx],
+      [wallet.payer]
+    );
+    await connection.sendTransaction(setFeeHandlerTx);
+    await sleep(2);
+
+    const accruedFees = await ctx.getAccruedFees(SYSTEM_PROGRAM_ID);
+    const feeHandlerBalanceBeforeClaim = await connection.getBalance(
+      feeHandler.publicKey
+    );
+
+    const claimIx = await getClaimFeesIx(
+      SYSTEM_PROGRAM_ID,
+      feeHandler.publicKey
+    );
+    const claimTx = await txnHelpers.buildV0Txn([claimIx], [feeHandler]);
+    await connection.sendTransaction(claimTx);
+    await sleep(2);
+
+    // fee handler should receive the accrued fees
+    const feeHandlerBalanceAfterClaim = await connection.getBalance(
+      feeHandler.publicKey
+    );
+    assert.equal(
+      feeHandlerBalanceAfterClaim,
+      feeHandlerBalanceBeforeClaim + accruedFees - 5000
+    );
+    assert.equal(await ctx.getAccruedFees(SYSTEM_PROGRAM_ID), 0);
+  });
+
+  it("should fail to claim fees when signer is not the fee handler", async () => {
+    const signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const claimIx = await getClaimFeesIx(mintKey, signer.publicKey);
+    const claimTx = await txnHelpers.buildV0Txn([claimIx], [signer]);
 
     try {
-      await connection.sendTransaction(tx);
+      await connection.sendTransaction(claimTx);
     } catch (err) {
       expect(err.message).to.includes("Fee handler account is not valid");
     }
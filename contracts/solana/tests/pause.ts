@@ -0,0 +1,149 @@
+import * as anchor from "@coral-xyz/anchor";
+import { assert, expect } from "chai";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+
+import { TxnHelpers } from "../scripts/utils/transaction";
+import {
+  intentProgram,
+  wallet,
+  connection,
+  getSwapIx,
+  getCancelIx,
+  getSetGuardianIx,
+  getPauseIx,
+  getUnpauseIx,
+  PAUSE_SWAP,
+  PAUSE_FILL,
+} from "../scripts/setup";
+import { sleep } from "../scripts/utils";
+import { TestContext } from "./setup";
+
+describe("Pause", async () => {
+  let srcNid = "solana";
+  let dstNid = "icon";
+
+  const ctx = new TestContext(srcNid, dstNid);
+  const txnHelpers = new TxnHelpers(connection, wallet.payer);
+  const guardian = Keypair.generate();
+
+  const newSwap = (creator: Keypair) => {
+    return {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: Keypair.generate().publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new Uint8Array()),
+    };
+  };
+
+  before(async () => {
+    await txnHelpers.airdrop(guardian.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const setGuardianIx = await getSetGuardianIx(
+      guardian.publicKey,
+      wallet.publicKey
+    );
+    const tx = await txnHelpers.buildV0Txn([setGuardianIx], [wallet.payer]);
+    await connection.sendTransaction(tx);
+    await sleep(2);
+  });
+
+  it("should fail when signer is not admin or guardian", async () => {
+    const signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const pauseIx = await getPauseIx(PAUSE_SWAP, signer.publicKey);
+    const pauseTx = await txnHelpers.buildV0Txn([pauseIx], [signer]);
+
+    try {
+      await connection.sendTransaction(pauseTx);
+    } catch (err) {
+      expect(err.message).to.includes("Only admin or guardian");
+    }
+  });
+
+  it("should pause swap by guardian and unpause by admin", async () => {
+    const pauseIx = await getPauseIx(PAUSE_SWAP, guardian.publicKey);
+    const pauseTx = await txnHelpers.buildV0Txn([pauseIx], [guardian]);
+    await connection.sendTransaction(pauseTx);
+    await sleep(2);
+
+    let config = await ctx.getConfig();
+    assert.equal(config.paused, PAUSE_SWAP);
+
+    // swap must fail while paused
+    const creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const swapIx = await getSwapIx(newSwap(creator));
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    try {
+      await connection.sendTransaction(swapTx);
+    } catch (err) {
+      expect(err.message).to.includes("Instruction is paused");
+    }
+
+    // guardian can not unpause
+    const guardianUnpauseIx = await getUnpauseIx(
+      PAUSE_SWAP,
+      guardian.publicKey
+    );
+    const guardianUnpauseTx = await txnHelpers.buildV0Txn(
+      [guardianUnpauseIx],
+      [guardian]
+    );
+    try {
+      await connection.sendTransaction(guardianUnpauseTx);
+    } catch (err) {
+      expect(err.message).to.includes("Only Admin");
+    }
+
+    const unpauseIx = await getUnpauseIx(PAUSE_SWAP, wallet.publicKey);
+    const unpauseTx = await txnHelpers.buildV0Txn([unpauseIx], [wallet.payer]);
+    await connection.sendTransaction(unpauseTx);
+    await sleep(2);
+
+    config = await ctx.getConfig();
+    assert.equal(config.paused, 0);
+  });
+
+  it("should cancel the order while fill is paused", async () => {
+    const creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let swap = newSwap(creator);
+    const swapIx = await getSwapIx(swap);
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    await connection.sendTransaction(swapTx);
+    await sleep(2);
+
+    const pauseIx = await getPauseIx(PAUSE_FILL, wallet.publicKey);
+    const pauseTx = await txnHelpers.buildV0Txn([pauseIx], [wallet.payer]);
+    await connection.sendTransaction(pauseTx);
+    await sleep(2);
+
+    const config = await ctx.getConfig();
+    swap.id = config.depositId;
+
+    const cancelIx = await getCancelIx(swap);
+    const cancelTx = await txnHelpers.buildV0Txn([cancelIx], [creator]);
+    await connection.sendTransaction(cancelTx);
+    await sleep(2);
+
+    // cancel message should be sent
+    const afterConfig = await ctx.getConfig();
+    assert.equal(afterConfig.connSn.toNumber(), config.connSn.toNumber() + 1);
+
+    const unpauseIx = await getUnpauseIx(PAUSE_FILL, wallet.publicKey);
+    const unpauseTx = await txnHelpers.buildV0Txn([unpauseIx], [wallet.payer]);
+    await connection.sendTransaction(unpauseTx);
+    await sleep(2);
+  });
+});
@@ -10,6 +10,9 @@ import {
   connection,
   getSwapIx,
   getRecvMessageIx,
+  getSetVerifierSetIx,
+  getAttestationIxs,
+  IntentPda,
 } from "../scripts/setup";
 import {
   MessageType,
@@ -135,7 +138,7 @@ describe("Receive Message", async () => {
     }
   });
 
-  it("should fail when signer is not an admin", async () => {
+  it("should fail when signer is not the relayer", async () => {
     let creator = Keypair.generate();
     await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
 
@@ -250,4 +253,185 @@ describe("Receive Message", async () => {
       vaultBalanceBeforeResolve - swapOrder.amount
     );
   });
+
+  it("should reject a partial fill", async () => {
+    let creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: Keypair.generate().publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount: new anchor.BN(1000000000),
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount: new anchor.BN(1000000000),
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    const swapIx = await getSwapIx(swap);
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    await connection.sendTransaction(swapTx);
+    await sleep(2);
+
+    const config = await ctx.getConfig();
+    swap.id = config.depositId;
+    const swapOrder = SwapOrder.from(swap);
+
+    // a partial fill would release the whole escrow to the solver
+    const solver = Keypair.generate();
+    const fillMessage = new OrderFill(
+      swapOrder.id,
+      swapOrder.encode(),
+      solver.publicKey.toString(),
+      BigInt(400000000)
+    );
+    const orderMessage = new OrderMessage(
+      MessageType.FILL,
+      fillMessage.encode()
+    );
+
+    const connSn = 3;
+    const recvMessageIx = await getRecvMessageIx(
+      dstNid,
+      connSn,
+      swapOrder,
+      Buffer.from(orderMessage.encode()),
+      MessageType.FILL,
+      ctx.admin.publicKey,
+      solver.publicKey.toString()
+    );
+    const recvMessageTx = await txnHelpers.buildV0Txn(
+      [recvMessageIx],
+      [ctx.admin]
+    );
+    try {
+      await connection.sendTransaction(recvMessageTx);
+    } catch (err) {
+      expect(err.message).to.includes("Partial fills are not supported");
+    }
+
+    const orderAccount = await connection.getAccountInfo(
+      IntentPda.order(swapOrder.id).pda
+    );
+    assert.isNotNull(orderAccount);
+  });
+
+  it("should receive message attested by the verifier set", async () => {
+    let creator = Keypair.generate();
+    await txnHelpers.airdrop(creator.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let relayer = Keypair.generate();
+    await txnHelpers.airdrop(relayer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    let verifiers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const setVerifierSetIx = await getSetVerifierSetIx(
+      verifiers.map((verifier) => verifier.publicKey),
+      2,
+      ctx.admin.publicKey
+    );
+    const setVerifierSetTx = await txnHelpers.buildV0Txn(
+      [setVerifierSetIx],
+      [ctx.admin]
+    );
+    await connection.sendTransaction(setVerifierSetTx);
+    await sleep(2);
+
+    let destination = Keypair.generate();
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: creator.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount,
+      toToken: SYSTEM_PROGRAM_ID.toString(),
+      toAmount,
+      data: Buffer.from(new Uint8Array()),
+    };
+    const swapIx = await getSwapIx(swap);
+    const swapTx = await txnHelpers.buildV0Txn([swapIx], [creator]);
+    await connection.sendTransaction(swapTx);
+    await sleep(2);
+
+    const config = await ctx.getConfig();
+    swap.id = config.depositId;
+    const swapOrder = SwapOrder.from(swap);
+
+    const cancelMessage = new OrderCancel(swapOrder.encode());
+    const orderMessage = Buffer.from(
+      new OrderMessage(MessageType.CANCEL, cancelMessage.encode()).encode()
+    );
+
+    const connSn = 100;
+    const recvMessageIx = await getRecvMessageIx(
+      srcNid,
+      connSn,
+      swapOrder,
+      orderMessage,
+      MessageType.CANCEL,
+      relayer.publicKey,
+      ""
+    );
+
+    // a single attestation doesn't meet the threshold
+    const underAttestedTx = await txnHelpers.buildV0Txn(
+      [
+        ...getAttestationIxs(
+          config.networkId,
+          srcNid,
+          connSn,
+          orderMessage,
+          verifiers.slice(0, 1)
+        ),
+        recvMessageIx,
+      ],
+      [relayer]
+    );
+    try {
+      await connection.sendTransaction(underAttestedTx);
+    } catch (err) {
+      expect(err.message).to.includes("Not enough verifier attestations");
+    }
+
+    const recvMessageTx = await txnHelpers.buildV0Txn(
+      [
+        ...getAttestationIxs(
+          config.networkId,
+          srcNid,
+          connSn,
+          orderMessage,
+          verifiers.slice(0, 2)
+        ),
+        recvMessageIx,
+      ],
+      [relayer]
+    );
+    await connection.sendTransaction(recvMessageTx);
+    await sleep(2);
+
+    const configAfter = await ctx.getConfig();
+    assert.equal(configAfter.connSn.toNumber(), config.connSn.toNumber() + 1);
+
+    // disable the verifier set for the other tests
+    const resetVerifierSetIx = await getSetVerifierSetIx(
+      [],
+      0,
+      ctx.admin.publicKey
+    );
+    const resetVerifierSetTx = await txnHelpers.buildV0Txn(
+      [resetVerifierSetIx],
+      [ctx.admin]
+    );
+    await connection.sendTransaction(resetVerifierSetTx);
+    await sleep(2);
+  });
 });
@@ -1,9 +1,22 @@
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
   createMint,
+  ExtensionType,
   getAccount,
+  getMintLen,
   getOrCreateAssociatedTokenAccount,
   mintTo,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
 import { IntentPda, intentProgram, wallet, connection } from "../scripts/setup";
@@ -14,7 +27,8 @@ export class TestContext {
   dstNID: string;
   admin: Keypair;
   mintKey: PublicKey;
-  orderAccountRent: number;
+  orderIndexAccountRent: number;
+  emptyOrderIndexAccountRent: number;
   vaultNativeAccountRent: number;
   orderFinishedAccountRent: number;
 
@@ -29,13 +43,37 @@ export class TestContext {
   }
 
   async setup() {
-    this.orderAccountRent = await connection.getMinimumBalanceForRentExemption(
-      409
-    );
+    // order index account holding a single order and no orders
+    this.orderIndexAccountRent =
+      await connection.getMinimumBalanceForRentExemption(29);
+    this.emptyOrderIndexAccountRent =
+      await connection.getMinimumBalanceForRentExemption(13);
     this.vaultNativeAccountRent =
       await connection.getMinimumBalanceForRentExemption(9);
     this.orderFinishedAccountRent =
-      await connection.getMinimumBalanceForRentExemption(10);
+      await connection.getMinimumBalanceForRentExemption(42);
+  }
+
+  /** Rent of the order account, which is sized from the order stored by `swap` */
+  async getOrderAccountRent(swap: any) {
+    const strings = [
+      intentProgram.programId.toString(),
+      this.srcNID,
+      swap.dstNid,
+      swap.creator,
+      swap.destinationAddress,
+      swap.token,
+      swap.toToken,
+    ];
+    const orderSize =
+      16 * 3 +
+      strings.reduce((size, s) => size + 4 + Buffer.byteLength(s), 0) +
+      4 +
+      swap.data.length;
+
+    return await connection.getMinimumBalanceForRentExemption(
+      8 + orderSize + 1
+    );
   }
 
   async createMint() {
@@ -51,20 +89,72 @@ export class TestContext {
     return mintKey;
   }
 
-  async mintToken(toAddress: PublicKey, amount: number) {
+  /** Creates a Token-2022 mint with the transfer fee extension */
+  async createTransferFeeMint(feeBasisPoints: number, maxFee: bigint) {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(
+      mintLen
+    );
+
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: wallet.publicKey,
+        newAccountPubkey: mintKeypair.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mintKeypair.publicKey,
+        wallet.publicKey,
+        wallet.publicKey,
+        feeBasisPoints,
+        maxFee,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mintKeypair.publicKey,
+        9,
+        wallet.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await sendAndConfirmTransaction(connection, tx, [
+      wallet.payer,
+      mintKeypair,
+    ]);
+
+    return mintKeypair.publicKey;
+  }
+
+  async mintToken(
+    toAddress: PublicKey,
+    amount: number,
+    mintKey: PublicKey = this.mintKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
     const tokenAccount = await getOrCreateAssociatedTokenAccount(
       connection,
       wallet.payer,
-      this.mintKey,
-      toAddress
+      mintKey,
+      toAddress,
+      false,
+      undefined,
+      undefined,
+      tokenProgram
     );
     await mintTo(
       connection,
       wallet.payer,
-      this.mintKey,
+      mintKey,
       tokenAccount.address,
       wallet.publicKey,
-      amount * LAMPORTS_PER_SOL
+      amount * LAMPORTS_PER_SOL,
+      [],
+      undefined,
+      tokenProgram
     );
     return tokenAccount;
   }
@@ -79,14 +169,38 @@ export class TestContext {
     );
   }
 
+  async getAccruedFees(mint: PublicKey) {
+    const fees = await intentProgram.methods
+      .queryAccruedFees(mint)
+      .accountsStrict({
+        feeVault: IntentPda.feeVault(mint).pda,
+      })
+      .view();
+    return Number(fees);
+  }
+
+  async getOrderIndex(creator: PublicKey) {
+    return await intentProgram.account.orderIndex.fetch(
+      IntentPda.orderIndex(creator).pda
+    );
+  }
+
   async getOrderFinishedAccount(swapOrder: SwapOrder) {
     return await intentProgram.account.orderFinished.fetch(
       IntentPda.orderFinished(swapOrder).pda
     );
   }
 
-  async getVaultTokenAccount() {
-    return await getAccount(connection, IntentPda.vaultToken(this.mintKey).pda);
+  async getVaultTokenAccount(
+    mintKey: PublicKey = this.mintKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) {
+    return await getAccount(
+      connection,
+      IntentPda.vaultToken(mintKey).pda,
+      undefined,
+      tokenProgram
+    );
   }
 
   calculateSwapFee(protocolFee: number, amount: number) {
@@ -2,7 +2,11 @@ import * as anchor from "@coral-xyz/anchor";
 import { assert, expect } from "chai";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
-import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  getAccount,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 
 import { TxnHelpers } from "../scripts/utils/transaction";
 import {
@@ -12,7 +16,7 @@ import {
   connection,
   getSwapIx,
 } from "../scripts/setup";
-import { SwapOrder } from "../scripts/types";
+import { DutchAuction, OrderData, OrderHook } from "../scripts/types";
 import { sleep } from "../scripts/utils";
 import { TestContext } from "./setup";
 
@@ -79,6 +83,104 @@ describe("Swap", async () => {
     );
   });
 
+  it("swap token 2022 with transfer fee", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    // 1% transfer fee
+    const feeMintKey = await ctx.createTransferFeeMint(100, BigInt(1000000000));
+
+    const destination = Keypair.generate();
+    let signerTokenBalance = 1000000000 * 100;
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+
+    const signerTokenAccount = await ctx.mintToken(
+      signer.publicKey,
+      100,
+      feeMintKey,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: signer.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: feeMintKey.toString(),
+      amount,
+      toToken: feeMintKey.toString(),
+      toAmount,
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    let swapIx = await getSwapIx(swap);
+    let swapTx = await txnHelpers.buildV0Txn([swapIx], [signer]);
+    await connection.sendTransaction(swapTx);
+    await sleep(2);
+
+    // signer pays the transfer fee on top of the order amount
+    let transferFee = 10101011;
+    let expectedBalance = signerTokenBalance - amount.toNumber() - transferFee;
+    let signerAccount = await getAccount(
+      connection,
+      signerTokenAccount.address,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(signerAccount.amount.toString(), expectedBalance.toString());
+
+    // vault escrows the full order amount
+    const tokenVaultAccount = await ctx.getVaultTokenAccount(
+      feeMintKey,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(tokenVaultAccount.amount.toString(), amount.toString());
+  });
+
+  it("swap orders with same amounts and index them by creator", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const destination = Keypair.generate();
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: signer.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount,
+      toToken: mintKey.toString(),
+      toAmount,
+      data: Buffer.from(new Uint8Array()),
+    };
+
+    let depositIds = [];
+    for (let i = 0; i < 2; i++) {
+      let swapIx = await getSwapIx(swap);
+      let swapTx = await txnHelpers.buildV0Txn([swapIx], [signer]);
+      await connection.sendTransaction(swapTx);
+      await sleep(2);
+
+      const config = await ctx.getConfig();
+      depositIds.push(config.depositId.toNumber());
+    }
+
+    // both orders should be open and listed in the creator order index
+    const orderIndex = await ctx.getOrderIndex(signer.publicKey);
+    assert.deepEqual(
+      orderIndex.orders.map((id) => id.toNumber()),
+      depositIds
+    );
+  });
+
   it("swap native token", async () => {
     let signer = Keypair.generate();
     await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
@@ -113,7 +215,11 @@ describe("Swap", async () => {
 
     // check signer SOL balance
     let expectedBalance =
-      signerPrevBalance - (amount.toNumber() + ctx.orderAccountRent + 5000);
+      signerPrevBalance -
+      (amount.toNumber() +
+        (await ctx.getOrderAccountRent(swap)) +
+        ctx.orderIndexAccountRent +
+        5000);
     let signerBalance = await connection.getBalance(signer.publicKey);
     assert.equal(signerBalance.toString(), expectedBalance.toString());
 
@@ -136,6 +242,80 @@ describe("Swap", async () => {
     );
   });
 
+  it("swap orders with cross-chain addresses and large data", async () => {
+    // EVM, Stellar and Cosmos-style destination and token addresses
+    const destinations = [
+      {
+        dstNid: "0x38.bsc",
+        destinationAddress: "0x" + "ab".repeat(20),
+        toToken: "0x" + "cd".repeat(20),
+      },
+      {
+        dstNid: "stellar",
+        destinationAddress:
+          "GCKTVSVNMGFWR4IOJOSOMFVTSN6SPMOWV2V6UNZFA2LX7QQCHAV2RJAH",
+        toToken: "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA",
+      },
+      {
+        dstNid: "archway",
+        destinationAddress: "archway1" + "q".repeat(58),
+        toToken: "ibc/" + "A".repeat(64),
+      },
+    ];
+
+    for (const { dstNid, destinationAddress, toToken } of destinations) {
+      let signer = Keypair.generate();
+      await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+      // order data with a hook carrying a several hundred byte payload
+      const hook = new OrderHook(
+        Keypair.generate().publicKey,
+        [],
+        Buffer.alloc(300, 0xab),
+        true
+      );
+      const data = Buffer.from(new OrderData(0, hook).encode());
+
+      let swap = {
+        id: new anchor.BN(1),
+        emitter: intentProgram.programId.toString(),
+        srcNid,
+        dstNid,
+        creator: signer.publicKey.toString(),
+        destinationAddress,
+        token: SYSTEM_PROGRAM_ID.toString(),
+        amount: new anchor.BN(1000000000),
+        toToken,
+        toAmount: new anchor.BN(1000000000),
+        data,
+      };
+
+      let swapIx = await getSwapIx(swap);
+      let swapTx = await txnHelpers.buildV0Txn([swapIx], [signer]);
+      await connection.sendTransaction(swapTx);
+      await sleep(2);
+
+      // order account should be sized from the order
+      const config = await ctx.getConfig();
+      const orderPda = IntentPda.order(config.depositId.toNumber()).pda;
+      const orderAccount = await intentProgram.account.orderAccount.fetch(
+        orderPda
+      );
+      assert.equal(orderAccount.order.destinationAddress, destinationAddress);
+      assert.equal(orderAccount.order.toToken, toToken);
+      assert.equal(
+        Buffer.from(orderAccount.order.data).toString("hex"),
+        data.toString("hex")
+      );
+
+      const orderAccountInfo = await connection.getAccountInfo(orderPda);
+      assert.equal(
+        orderAccountInfo.lamports,
+        await ctx.getOrderAccountRent(swap)
+      );
+    }
+  });
+
   it("should fail when source network id is invalid", async () => {
     let signer = Keypair.generate();
     await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
@@ -167,6 +347,39 @@ describe("Swap", async () => {
     }
   });
 
+  it("should fail when auction starts below the order to amount", async () => {
+    let signer = Keypair.generate();
+    await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
+
+    const destination = Keypair.generate();
+    let amount = new anchor.BN(1000000000);
+    let toAmount = new anchor.BN(1000000000);
+    let now = Math.floor(Date.now() / 1000);
+    let auction = new DutchAuction(BigInt(900000000), now, now + 600);
+
+    let swap = {
+      id: new anchor.BN(1),
+      emitter: intentProgram.programId.toString(),
+      srcNid,
+      dstNid,
+      creator: signer.publicKey.toString(),
+      destinationAddress: destination.publicKey.toString(),
+      token: SYSTEM_PROGRAM_ID.toString(),
+      amount,
+      toToken: mintKey.toString(),
+      toAmount,
+      data: Buffer.from(new OrderData(0, undefined, auction).encode()),
+    };
+
+    let swapIx = await getSwapIx(swap);
+    let swapTx = await txnHelpers.buildV0Txn([swapIx], [signer]);
+    try {
+      await connection.sendTransaction(swapTx);
+    } catch (err) {
+      expect(err.message).to.includes("Auction parameters are not valid");
+    }
+  });
+
   it("should fail when creator address doesn't match with signer", async () => {
     let signer = Keypair.generate();
     await txnHelpers.airdrop(signer.publicKey, LAMPORTS_PER_SOL * 10);
@@ -188,7 +401,7 @@ describe("Swap", async () => {
       toAmount,
       data: Buffer.from(new Uint8Array()),
     };
-    let swapOrder = SwapOrder.from(swap);
+    const config = await ctx.getConfig();
 
     let swapIx = await intentProgram.methods
       .swap(swap)
@@ -196,12 +409,8 @@ describe("Swap", async () => {
         systemProgram: SYSTEM_PROGRAM_ID,
         signer: signer.publicKey,
         config: IntentPda.config().pda,
-        orderAccount: IntentPda.order(
-          signer.publicKey,
-          swapOrder.dstNID,
-          amount.toNumber(),
-          toAmount.toNumber()
-        ).pda,
+        orderAccount: IntentPda.order(config.depositId.toNumber() + 1).pda,
+        orderIndex: IntentPda.orderIndex(signer.publicKey).pda,
         nativeVaultAccount: IntentPda.vaultNative().pda,
         tokenVaultAccount: null,
         signerTokenAccount: null,
@@ -18,6 +18,8 @@ pub fn cancel_order(env: &Env, id: u128) -> Result<(), ContractError> {
         return Ok(());
     }
 
+    request_cancel(&env, id);
+
     let cancel = Cancel::new(order.encode(&env));
     let order_msg = OrderMessage::new(MessageType::CANCEL, cancel.encode(&env));
     GeneralizedConnection::send_message(&env, order.dst_nid(), order_msg.encode(&env));
@@ -30,7 +32,7 @@ pub fn resolve_cancel(
     src_network: String,
     order_bytes: Bytes,
 ) -> Result<(), ContractError> {
-    let order = SwapOrder::decode(&env, order_bytes.clone());
+    let order = SwapOrder::decode(&env, order_bytes.clone())?;
 
     let order_hash = &order.get_hash(&env);
     if storage::order_finished(&env, order_hash) {
@@ -41,9 +43,23 @@ pub fn resolve_cancel(
         return Err(ContractError::InvalidNetwork);
     }
 
+    // The refund of an order created on this network is resolved like the cross-chain ones,
+    // by the fill sent below
+    if order.src_nid() == storage::nid(&env)? {
+        request_cancel(&env, order.id());
+    }
+
+    let pending = storage::pending_fill(&env, order_hash);
+    storage::remove_pending_fill(&env, order_hash);
     storage::store_finished_order(&env, order_hash);
 
-    let fill = OrderFill::new(order.id(), order_bytes, order.creator());
+    // Refund only the part of a partially filled order that is still open
+    let fill = match pending {
+        Some(remaining) => {
+            OrderFill::new_partial(order.id(), order_bytes, order.creator(), remaining)
+        }
+        None => OrderFill::new(order.id(), order_bytes, order.creator()),
+    };
     let msg = OrderMessage::new(MessageType::FILL, fill.encode(&env));
 
     GeneralizedConnection::send_message(&env, order.src_nid(), msg.encode(&env));
@@ -51,3 +67,11 @@ pub fn resolve_cancel(
 
     Ok(())
 }
+
+/// Marks an order created on this network as waiting for its refund
+fn request_cancel(env: &Env, id: u128) {
+    if let Ok(mut status) = storage::get_order_status(&env, id) {
+        status.set_state(&env, OrderState::CancelRequested);
+        storage::store_order_status(&env, id, &status);
+    }
+}
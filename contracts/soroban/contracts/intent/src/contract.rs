@@ -1,10 +1,10 @@
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Vec};
 
 use crate::{
     cancel,
     connection::GeneralizedConnection,
     error::ContractError,
-    fill, helpers,
+    event, fill, helpers,
     interfaces::{IGeneralizedConnection, IIntent},
     storage, swap,
     types::*,
@@ -28,6 +28,7 @@ impl IIntent for Intent {
         storage::store_network_id(&env, &network_id);
         storage::store_fee_handler(&env, &fee_handler);
         storage::store_upgrade_authority(&env, &upgrade_authority);
+        storage::enforce_network_registry(&env);
 
         Ok(())
     }
@@ -36,13 +37,32 @@ impl IIntent for Intent {
         swap::swap_order(&env, order)
     }
 
+    fn swap_signed(
+        env: Env,
+        order: SwapOrder,
+        nonce: u128,
+        deadline: u64,
+    ) -> Result<(), ContractError> {
+        swap::swap_order_signed(&env, order, nonce, deadline)
+    }
+
     fn fill(
         env: Env,
         order: SwapOrder,
         sender: Address,
         solver_address: String,
     ) -> Result<(), ContractError> {
-        fill::fill_order(&env, order, sender, solver_address)
+        fill::fill_order(&env, order, sender, solver_address, None)
+    }
+
+    fn partial_fill(
+        env: Env,
+        order: SwapOrder,
+        sender: Address,
+        solver_address: String,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        fill::fill_order(&env, order, sender, solver_address, Some(amount))
     }
 
     fn cancel(env: Env, id: u128) -> Result<(), ContractError> {
@@ -57,14 +77,14 @@ impl IIntent for Intent {
     ) -> Result<(), ContractError> {
         GeneralizedConnection::recv_message(&env, src_network.clone(), conn_sn)?;
 
-        let msg = OrderMessage::decode(&env, msg);
+        let msg = OrderMessage::decode(&env, msg)?;
         match msg.message_type() {
             MessageType::FILL => {
-                let fill = OrderFill::decode(&env, msg.message());
+                let fill = OrderFill::decode(&env, msg.message())?;
                 fill::resolve_fill(&env, src_network, fill)
             }
             MessageType::CANCEL => {
-                let cancel = Cancel::decode(&env, msg.message());
+                let cancel = Cancel::decode(&env, msg.message())?;
                 cancel::resolve_cancel(&env, src_network, cancel.order_bytes())
             }
         }
@@ -95,16 +115,58 @@ impl IIntent for Intent {
         Ok(())
     }
 
+    fn set_network(
+        env: Env,
+        network_id: String,
+        config: NetworkConfig,
+    ) -> Result<(), ContractError> {
+        helpers::ensure_admin(&env)?;
+        storage::store_network(&env, &network_id, &config);
+        event::network_updated(
+            &env,
+            network_id,
+            config.enabled(),
+            config.address_format(),
+            config.partial_fills(),
+        );
+
+        Ok(())
+    }
+
+    fn extend_order_ttl(env: Env, id: u128) -> Result<(), ContractError> {
+        storage::extend_order_ttl(&env, id)
+    }
+
+    fn extend_fill_ttl(env: Env, order_hash: BytesN<32>) -> Result<(), ContractError> {
+        storage::extend_fill_ttl(&env, &order_hash)
+    }
+
     fn get_order(env: Env, id: u128) -> Result<SwapOrder, ContractError> {
         storage::get_order(&env, id)
     }
 
+    fn get_order_status(env: Env, id: u128) -> Result<OrderStatus, ContractError> {
+        storage::get_order_status(&env, id)
+    }
+
+    fn get_network(env: Env, network_id: String) -> Result<NetworkConfig, ContractError> {
+        storage::network(&env, &network_id).ok_or(ContractError::NetworkNotSupported)
+    }
+
+    fn get_networks(env: Env) -> Vec<String> {
+        storage::network_ids(&env)
+    }
+
     fn get_finished_order(env: Env, bytes: BytesN<32>) -> Result<bool, ContractError> {
         let order_finished = storage::order_finished(&env, &bytes);
 
         Ok(order_finished)
     }
 
+    fn get_pending_fill(env: Env, bytes: BytesN<32>) -> Option<u128> {
+        storage::pending_fill(&env, &bytes)
+    }
+
     fn get_receipt(env: Env, network_id: String, conn_sn: u128) -> bool {
         GeneralizedConnection::get_receipt(&env, network_id, conn_sn)
     }
@@ -14,4 +14,11 @@ pub enum ContractError {
     OrderAlreadyFilled = 8,
     OrderMismatched = 9,
     InvalidNetwork = 10,
+    InvalidFillAmount = 11,
+    DecodeFailed = 12,
+    InvalidListLength = 13,
+    InvalidMessageType = 14,
+    InvalidNonce = 15,
+    PermitExpired = 16,
+    PartialFillNotSupported = 17,
 }
@@ -2,6 +2,8 @@
 
 use soroban_sdk::{contracttype, Bytes, Env, String};
 
+use crate::types::AddressFormat;
+
 /// Emitted when a new swap intent is created
 #[contracttype]
 pub struct SwapIntent {
@@ -54,6 +56,19 @@ pub struct OrderClosed {
     pub id: u128,
 }
 
+// Emitted when a network is added to the registry or its settings are updated
+#[contracttype]
+pub struct NetworkUpdated {
+    // The ID of the network
+    pub nid: String,
+    // Whether swaps and fills are accepted for the network
+    pub enabled: bool,
+    // The encoding of the addresses of the network
+    pub addressFormat: AddressFormat,
+    // Whether the network settles partial fills
+    pub partialFills: bool,
+}
+
 /// Emitted when a cross-chain message is sent
 #[contracttype]
 pub struct Message {
@@ -113,6 +128,23 @@ pub fn order_cancelled(e: &Env, id: u128, srcNID: String) {
     e.events().publish(("OrderCancelled",), emit_message);
 }
 
+pub fn network_updated(
+    e: &Env,
+    nid: String,
+    enabled: bool,
+    addressFormat: AddressFormat,
+    partialFills: bool,
+) {
+    let emit_message = NetworkUpdated {
+        nid,
+        enabled,
+        addressFormat,
+        partialFills,
+    };
+
+    e.events().publish(("NetworkUpdated",), emit_message);
+}
+
 pub fn send_message(e: &Env, targetNetwork: String, sn: u128, msg: Bytes) {
     let emit_message = Message {
         targetNetwork,
@@ -10,8 +10,10 @@ pub fn fill_order(
     order: SwapOrder,
     sender: Address,
     solver_address: String,
+    amount: Option<u128>,
 ) -> Result<(), ContractError> {
     sender.require_auth();
+    helpers::ensure_network_supported(&env, &order.src_nid())?;
 
     let order_bytes = order.encode(&env);
     let order_hash = helpers::hash_data(&env, &order_bytes);
@@ -19,20 +21,45 @@ pub fn fill_order(
     if storage::order_finished(&env, &order_hash) {
         return Err(ContractError::OrderAlreadyFilled);
     }
-    storage::store_finished_order(&env, &order_hash);
+
+    let pending = storage::pending_fill(&env, &order_hash);
+    let remaining = pending.unwrap_or(order.to_amount());
+    let fill_amount = amount.unwrap_or(remaining);
+    if amount == Some(0) || fill_amount > remaining {
+        return Err(ContractError::InvalidFillAmount);
+    }
+
+    // An order settled by a single fill keeps the original message format, the other fills
+    // are only sent to networks settling partial fills
+    let partial = pending.is_some() || fill_amount < remaining;
+    if partial {
+        helpers::ensure_partial_fill_supported(&env, &order.src_nid())?;
+    }
+
+    let remaining = remaining - fill_amount;
+    if remaining == 0 {
+        storage::remove_pending_fill(&env, &order_hash);
+        storage::store_finished_order(&env, &order_hash);
+    } else {
+        storage::store_pending_fill(&env, &order_hash, remaining);
+    }
 
     let protocol_fee = storage::protocol_fee(&env);
     let fee_handler = storage::get_fee_handler(&env)?;
     let to_token = Address::from_string(&order.to_token());
     let to_address = Address::from_string(&order.dst_address());
 
-    let fee = (order.to_amount() * protocol_fee) / 10_000;
-    let to_amount = order.to_amount() - fee;
+    let fee = (fill_amount * protocol_fee) / 10_000;
+    let to_amount = fill_amount - fee;
 
     helpers::transfer_token(&env, &to_token, &sender, &fee_handler, fee);
     helpers::transfer_token(&env, &to_token, &sender, &to_address, to_amount);
 
-    let fill = OrderFill::new(order.id(), order_bytes, solver_address);
+    let fill = if partial {
+        OrderFill::new_partial(order.id(), order_bytes, solver_address, fill_amount)
+    } else {
+        OrderFill::new(order.id(), order_bytes, solver_address)
+    };
 
     if order.src_nid() == order.dst_nid() {
         let nid = storage::nid(&env)?;
@@ -58,16 +85,56 @@ pub fn resolve_fill(env: &Env, src_network: String, fill: OrderFill) -> Result<(
         return Err(ContractError::InvalidNetwork);
     }
 
-    storage::remove_order(&env, fill.id());
-    event::order_closed(&env, fill.id());
+    let remaining = storage::pending_order_amount(&env, fill.id()).unwrap_or(order.to_amount());
+    let fill_amount = fill.amount().unwrap_or(remaining);
+    if fill.amount() == Some(0) || fill_amount > remaining {
+        return Err(ContractError::InvalidFillAmount);
+    }
+
+    let filled = order.to_amount() - remaining;
+    let remaining = remaining - fill_amount;
+
+    let released_before = helpers::released_amount(&env, order.amount(), filled, order.to_amount());
+    let released_after = if remaining == 0 {
+        order.amount()
+    } else {
+        helpers::released_amount(
+            &env,
+            order.amount(),
+            filled + fill_amount,
+            order.to_amount(),
+        )
+    };
+
+    // The destination network confirms a cancel request with a fill paying the creator back,
+    // for the whole order or for the part left open by partial fills
+    let mut status = storage::get_order_status(&env, fill.id())?;
+    let cancel_requested = status.state() == OrderState::CancelRequested;
+    let state = match remaining {
+        _ if cancel_requested && fill.solver() == order.creator() => OrderState::Refunded,
+        0 => OrderState::Filled,
+        _ if cancel_requested => OrderState::CancelRequested,
+        _ => OrderState::PartiallyFilled,
+    };
+
+    if remaining == 0 {
+        storage::remove_order(&env, fill.id());
+        storage::remove_pending_order_amount(&env, fill.id());
+        event::order_closed(&env, fill.id());
+    } else {
+        storage::store_pending_order_amount(&env, fill.id(), remaining);
+    }
 
     helpers::transfer_token(
         &env,
         &Address::from_string(&order.token()),
         &env.current_contract_address(),
         &Address::from_string(&fill.solver()),
-        order.amount(),
+        released_after - released_before,
     );
 
+    status.settle(&env, state, fill.solver());
+    storage::store_order_status(&env, fill.id(), &status);
+
     Ok(())
 }
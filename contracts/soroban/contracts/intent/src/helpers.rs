@@ -1,6 +1,10 @@
-use soroban_sdk::{token, Address, Bytes, BytesN, Env};
+use soroban_rlp::encoder;
+use soroban_sdk::{token, vec, Address, Bytes, BytesN, Env, String, U256};
 
-use crate::{error::ContractError, storage};
+use crate::{error::ContractError, storage, types::SwapOrder};
+
+/// Domain of the permits authorizing signed orders
+const PERMIT_DOMAIN: &str = "SwapIntentPermit";
 
 pub fn ensure_admin(e: &Env) -> Result<Address, ContractError> {
     let admin = storage::admin(&e)?;
@@ -16,11 +20,89 @@ pub fn ensure_upgrade_authority(e: &Env) -> Result<Address, ContractError> {
     Ok(authority)
 }
 
+/// Checks that orders can be swapped between this network and `network_id`. Contracts
+/// upgraded from a version without the network registry were initialized without enforcing
+/// it, so they accept every network until the admin registers the first one. The flag is set
+/// by `initialize` rather than `upgrade`, as the old code runs the rest of an upgrade.
+pub fn ensure_network_supported(e: &Env, network_id: &String) -> Result<(), ContractError> {
+    if *network_id == storage::nid(&e)? || !storage::network_registry_enforced(&e) {
+        return Ok(());
+    }
+
+    match storage::network(&e, network_id) {
+        Some(config) if config.enabled() => Ok(()),
+        _ => Err(ContractError::NetworkNotSupported),
+    }
+}
+
+/// Checks that a partial fill of an order created on `network_id` can be settled there. The
+/// fills of other networks are sent as complete fills, which would release the whole order.
+pub fn ensure_partial_fill_supported(e: &Env, network_id: &String) -> Result<(), ContractError> {
+    if *network_id == storage::nid(&e)? {
+        return Ok(());
+    }
+
+    match storage::network(&e, network_id) {
+        Some(config) if config.partial_fills() => Ok(()),
+        _ => Err(ContractError::PartialFillNotSupported),
+    }
+}
+
 pub fn transfer_token(env: &Env, token: &Address, from: &Address, to: &Address, amount: u128) {
     let token_client = token::Client::new(&env, &token);
     token_client.transfer(&from, &to, &(amount as i128));
 }
 
+/// Pulls `amount` of `token` from the allowance given by `from` to the contract
+pub fn transfer_token_from(env: &Env, token: &Address, from: &Address, to: &Address, amount: u128) {
+    let token_client = token::Client::new(&env, &token);
+    token_client.transfer_from(
+        &env.current_contract_address(),
+        &from,
+        &to,
+        &(amount as i128),
+    );
+}
+
+/// Returns the hash authorized by the creator of an order submitted through `swap_signed`.
+/// It binds the order to this contract and network, so that the authorization cannot be
+/// replayed on another deployment.
+pub fn permit_hash(
+    e: &Env,
+    order: &SwapOrder,
+    nonce: u128,
+    deadline: u64,
+) -> Result<BytesN<32>, ContractError> {
+    let list = vec![
+        &e,
+        encoder::encode_string(&e, String::from_str(&e, PERMIT_DOMAIN)),
+        encoder::encode_string(&e, e.current_contract_address().to_string()),
+        encoder::encode_string(&e, storage::nid(&e)?),
+        encoder::encode(&e, order.encode(&e)),
+        encoder::encode_u128(&e, nonce),
+        encoder::encode_u128(&e, deadline.into()),
+    ];
+
+    Ok(hash_data(&e, &encoder::encode_list(&e, list, false)))
+}
+
 pub fn hash_data(e: &Env, data: &Bytes) -> BytesN<32> {
     e.crypto().keccak256(data).to_bytes()
 }
+
+/// Returns the share of the escrowed `amount` released once `filled` out of `to_amount`
+/// has been delivered on the destination chain
+pub fn released_amount(e: &Env, amount: u128, filled: u128, to_amount: u128) -> u128 {
+    if filled == 0 {
+        return 0;
+    }
+    if filled >= to_amount {
+        return amount;
+    }
+
+    U256::from_u128(e, amount)
+        .mul(&U256::from_u128(e, filled))
+        .div(&U256::from_u128(e, to_amount))
+        .to_u128()
+        .unwrap()
+}
@@ -1,6 +1,9 @@
-use soroban_sdk::{Address, Bytes, BytesN, Env, String};
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
 
-use crate::{error::ContractError, types::swap_order::SwapOrder};
+use crate::{
+    error::ContractError,
+    types::{network_config::NetworkConfig, order_status::OrderStatus, swap_order::SwapOrder},
+};
 pub trait IIntent {
     fn initialize(
         env: Env,
@@ -12,6 +15,13 @@ pub trait IIntent {
 
     fn swap(env: Env, order: SwapOrder) -> Result<(), ContractError>;
 
+    fn swap_signed(
+        env: Env,
+        order: SwapOrder,
+        nonce: u128,
+        deadline: u64,
+    ) -> Result<(), ContractError>;
+
     fn fill(
         env: Env,
         order: SwapOrder,
@@ -19,6 +29,14 @@ pub trait IIntent {
         solver_address: String,
     ) -> Result<(), ContractError>;
 
+    fn partial_fill(
+        env: Env,
+        order: SwapOrder,
+        sender: Address,
+        solver_address: String,
+        amount: u128,
+    ) -> Result<(), ContractError>;
+
     fn cancel(env: Env, id: u128) -> Result<(), ContractError>;
 
     fn recv_message(
@@ -36,10 +54,28 @@ pub trait IIntent {
 
     fn set_upgrade_authority(env: &Env, address: Address) -> Result<(), ContractError>;
 
+    fn set_network(
+        env: Env,
+        network_id: String,
+        config: NetworkConfig,
+    ) -> Result<(), ContractError>;
+
+    fn extend_order_ttl(env: Env, id: u128) -> Result<(), ContractError>;
+
+    fn extend_fill_ttl(env: Env, order_hash: BytesN<32>) -> Result<(), ContractError>;
+
     fn get_order(env: Env, id: u128) -> Result<SwapOrder, ContractError>;
 
+    fn get_order_status(env: Env, id: u128) -> Result<OrderStatus, ContractError>;
+
+    fn get_network(env: Env, network_id: String) -> Result<NetworkConfig, ContractError>;
+
+    fn get_networks(env: Env) -> Vec<String>;
+
     fn get_finished_order(env: Env, bytes: BytesN<32>) -> Result<bool, ContractError>;
 
+    fn get_pending_fill(env: Env, bytes: BytesN<32>) -> Option<u128>;
+
     fn get_receipt(env: Env, network_id: String, conn_sn: u128) -> bool;
 
     fn get_admin(env: Env) -> Result<Address, ContractError>;
@@ -1,17 +1,26 @@
-use soroban_sdk::{Address, BytesN, Env, String};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 use crate::{
     error::ContractError,
-    types::{storage_types::StorageKey, swap_order::SwapOrder},
+    types::{
+        network_config::NetworkConfig, order_status::OrderStatus, storage_types::StorageKey,
+        swap_order::SwapOrder,
+    },
 };
 
-const DAY_IN_LEDGERS: u32 = 17280; // assumes 5s a ledger
+pub(crate) const DAY_IN_LEDGERS: u32 = 17280; // assumes 5s a ledger
 
 const LEDGER_THRESHOLD_INSTANCE: u32 = DAY_IN_LEDGERS * 30; // ~ 30 days
 const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + DAY_IN_LEDGERS; // ~ 31 days
 
-const LEDGER_THRESHOLD_PERSISTENT: u32 = DAY_IN_LEDGERS * 30; // ~ 30 days
-const LEDGER_BUMP_PERSISTENT: u32 = LEDGER_THRESHOLD_PERSISTENT + DAY_IN_LEDGERS; // ~ 31 days
+// Open orders and their pending amounts, kept alive while the order can still be filled
+const LEDGER_THRESHOLD_ORDER: u32 = DAY_IN_LEDGERS * 30; // ~ 30 days
+pub(crate) const LEDGER_BUMP_ORDER: u32 = DAY_IN_LEDGERS * 90; // ~ 90 days
+
+// Finished orders, receipts and order statuses, kept alive longer as they protect against
+// replays and outlive the orders
+const LEDGER_THRESHOLD_REPLAY: u32 = DAY_IN_LEDGERS * 60; // ~ 60 days
+pub(crate) const LEDGER_BUMP_REPLAY: u32 = DAY_IN_LEDGERS * 120; // ~ 120 days
 
 pub fn is_initialized(e: &Env) -> Result<(), ContractError> {
     let initialized = e.storage().instance().has(&StorageKey::Admin);
@@ -37,6 +46,17 @@ pub fn get_order(e: &Env, id: u128) -> Result<SwapOrder, ContractError> {
         .ok_or(ContractError::OrderNotFound)
 }
 
+/// Returns the status of an order, orders created before statuses were tracked being
+/// reported as open
+pub fn get_order_status(e: &Env, id: u128) -> Result<OrderStatus, ContractError> {
+    let status = e.storage().persistent().get(&StorageKey::OrderStatus(id));
+    match status {
+        Some(status) => Ok(status),
+        None if e.storage().persistent().has(&StorageKey::Orders(id)) => Ok(OrderStatus::legacy()),
+        None => Err(ContractError::OrderNotFound),
+    }
+}
+
 pub fn get_receipt(e: &Env, network_id: String, conn_sn: u128) -> bool {
     let key = StorageKey::Receipts(network_id, conn_sn);
     let is_received = e.storage().persistent().get(&key).unwrap_or(false);
@@ -75,6 +95,47 @@ pub fn nid(e: &Env) -> Result<String, ContractError> {
         .ok_or(ContractError::Uninitialized)
 }
 
+pub fn network(e: &Env, network_id: &String) -> Option<NetworkConfig> {
+    e.storage()
+        .instance()
+        .get(&StorageKey::Networks(network_id.clone()))
+}
+
+pub fn network_ids(e: &Env) -> Vec<String> {
+    e.storage()
+        .instance()
+        .get(&StorageKey::NetworkIds)
+        .unwrap_or(Vec::new(e))
+}
+
+/// Whether swaps and fills are checked against the network registry, which is the case for
+/// contracts initialized with the registry and once the admin registers a network
+pub fn network_registry_enforced(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&StorageKey::NetworkRegistry)
+        .unwrap_or(false)
+}
+
+pub fn enforce_network_registry(e: &Env) {
+    e.storage()
+        .instance()
+        .set(&StorageKey::NetworkRegistry, &true);
+}
+
+pub fn store_network(e: &Env, network_id: &String, config: &NetworkConfig) {
+    enforce_network_registry(e);
+
+    let key = StorageKey::Networks(network_id.clone());
+    if !e.storage().instance().has(&key) {
+        let mut ids = network_ids(e);
+        ids.push_back(network_id.clone());
+        e.storage().instance().set(&StorageKey::NetworkIds, &ids);
+    }
+    e.storage().instance().set(&key, config);
+    extend_instance(e)
+}
+
 pub fn deposit_id(e: &Env) -> Result<u128, ContractError> {
     e.storage()
         .instance()
@@ -99,25 +160,30 @@ pub fn increment_conn_sn(e: &Env) -> u128 {
     let mut sn: u128 = e.storage().instance().get(&StorageKey::ConnSn).unwrap_or(0);
     sn += 1;
     e.storage().instance().set(&StorageKey::ConnSn, &sn);
+    extend_instance(e);
 
     sn
 }
 
 pub fn increment_deposit_id(e: &Env) -> u128 {
-    e.storage()
+    let id = e
+        .storage()
         .instance()
         .update(&StorageKey::DepositId, |value| -> u128 {
             if let Some(req_id) = value {
                 return req_id + 1;
             }
             1
-        })
+        });
+    extend_instance(e);
+
+    id
 }
 
 pub fn store_finished_order(e: &Env, order_hash: &BytesN<32>) {
-    e.storage()
-        .persistent()
-        .set(&StorageKey::FinishedOrders(order_hash.clone()), &true)
+    let key = StorageKey::FinishedOrders(order_hash.clone());
+    e.storage().persistent().set(&key, &true);
+    extend_persistent(e, &key);
 }
 
 pub fn remove_order(e: &Env, id: u128) {
@@ -131,19 +197,116 @@ pub fn order_finished(e: &Env, order_hash: &BytesN<32>) -> bool {
         .unwrap_or(false)
 }
 
+pub fn pending_fill(e: &Env, order_hash: &BytesN<32>) -> Option<u128> {
+    e.storage()
+        .persistent()
+        .get(&StorageKey::PendingFills(order_hash.clone()))
+}
+
+pub fn store_pending_fill(e: &Env, order_hash: &BytesN<32>, remaining: u128) {
+    let key = StorageKey::PendingFills(order_hash.clone());
+    e.storage().persistent().set(&key, &remaining);
+    extend_persistent(e, &key);
+}
+
+pub fn remove_pending_fill(e: &Env, order_hash: &BytesN<32>) {
+    e.storage()
+        .persistent()
+        .remove(&StorageKey::PendingFills(order_hash.clone()));
+}
+
+pub fn pending_order_amount(e: &Env, id: u128) -> Option<u128> {
+    e.storage()
+        .persistent()
+        .get(&StorageKey::PendingOrderAmount(id))
+}
+
+pub fn store_pending_order_amount(e: &Env, id: u128, remaining: u128) {
+    let key = StorageKey::PendingOrderAmount(id);
+    e.storage().persistent().set(&key, &remaining);
+    extend_persistent(e, &key);
+}
+
+pub fn remove_pending_order_amount(e: &Env, id: u128) {
+    e.storage()
+        .persistent()
+        .remove(&StorageKey::PendingOrderAmount(id));
+}
+
 pub fn store_receipt(e: &Env, network_id: String, conn_sn: u128) {
     let key = StorageKey::Receipts(network_id, conn_sn);
     e.storage().persistent().set(&key, &true);
     extend_persistent(e, &key);
 }
 
+pub fn nonce_used(e: &Env, creator: &Address, nonce: u128) -> bool {
+    e.storage()
+        .persistent()
+        .has(&StorageKey::UsedNonces(creator.clone(), nonce))
+}
+
+pub fn store_used_nonce(e: &Env, creator: &Address, nonce: u128) {
+    let key = StorageKey::UsedNonces(creator.clone(), nonce);
+    e.storage().persistent().set(&key, &true);
+    extend_persistent(e, &key);
+}
+
 pub fn store_admin(e: &Env, address: &Address) {
     e.storage().instance().set(&StorageKey::Admin, &address);
     extend_instance(&e);
 }
 
 pub fn store_order(e: &Env, id: u128, order: &SwapOrder) {
-    e.storage().persistent().set(&StorageKey::Orders(id), order);
+    let key = StorageKey::Orders(id);
+    e.storage().persistent().set(&key, order);
+    extend_persistent(e, &key);
+}
+
+pub fn store_order_status(e: &Env, id: u128, status: &OrderStatus) {
+    let key = StorageKey::OrderStatus(id);
+    e.storage().persistent().set(&key, status);
+    extend_persistent(e, &key);
+}
+
+/// Extends the TTL of an open order, of its status and of its pending amount, if partially
+/// filled.
+///
+/// An order whose TTL ran out is archived and must first be restored off-chain with a
+/// `RestoreFootprintOp` on its `Orders(id)` key (and `PendingOrderAmount(id)` key, if any),
+/// along with the contract instance if it was archived too, for instance through
+/// `stellar contract restore`. Restored entries only get the minimum TTL of the network,
+/// this call then extends them back to the order policy.
+pub fn extend_order_ttl(e: &Env, id: u128) -> Result<(), ContractError> {
+    let key = StorageKey::Orders(id);
+    if !e.storage().persistent().has(&key) {
+        return Err(ContractError::OrderNotFound);
+    }
+    extend_persistent(e, &key);
+
+    for key in [
+        StorageKey::OrderStatus(id),
+        StorageKey::PendingOrderAmount(id),
+    ] {
+        if e.storage().persistent().has(&key) {
+            extend_persistent(e, &key);
+        }
+    }
+    extend_instance(e);
+
+    Ok(())
+}
+
+/// Extends the TTL of the remaining amount of a partially filled order on its destination
+/// network, which is restored like `extend_order_ttl` once archived
+pub fn extend_fill_ttl(e: &Env, order_hash: &BytesN<32>) -> Result<(), ContractError> {
+    let key = StorageKey::PendingFills(order_hash.clone());
+    if !e.storage().persistent().has(&key) {
+        return Err(ContractError::OrderNotFound);
+    }
+    extend_persistent(e, &key);
+    extend_instance(e);
+
+    Ok(())
 }
 
 pub fn store_network_id(e: &Env, network_id: &String) {
@@ -176,8 +339,20 @@ pub fn extend_instance(e: &Env) {
         .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
 }
 
+/// Returns the TTL threshold and extension, in ledgers, of a persistent entry
+pub fn persistent_ttl(key: &StorageKey) -> (u32, u32) {
+    match key {
+        StorageKey::FinishedOrders(_)
+        | StorageKey::Receipts(..)
+        | StorageKey::OrderStatus(_)
+        | StorageKey::UsedNonces(..) => (LEDGER_THRESHOLD_REPLAY, LEDGER_BUMP_REPLAY),
+        _ => (LEDGER_THRESHOLD_ORDER, LEDGER_BUMP_ORDER),
+    }
+}
+
 pub fn extend_persistent(e: &Env, key: &StorageKey) {
+    let (threshold, extend_to) = persistent_ttl(key);
     e.storage()
         .persistent()
-        .extend_ttl(key, LEDGER_THRESHOLD_PERSISTENT, LEDGER_BUMP_PERSISTENT);
+        .extend_ttl(key, threshold, extend_to);
 }
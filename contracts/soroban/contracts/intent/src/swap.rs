@@ -1,23 +1,62 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, IntoVal};
 
 use crate::{error::ContractError, event, helpers, storage, types::*};
 
 pub fn swap_order(env: &Env, order: SwapOrder) -> Result<(), ContractError> {
-    let mut order = order;
     let contract_address = env.current_contract_address();
     let sender = Address::from_string(&order.creator());
 
     sender.require_auth();
+    helpers::ensure_network_supported(&env, &order.dst_nid())?;
 
     let token = Address::from_string(&order.token());
     helpers::transfer_token(&env, &token, &sender, &contract_address, order.amount());
 
+    open_order(&env, order)
+}
+
+/// Creates an order on behalf of its creator, who authorized the permit hash of the order
+/// instead of the whole invocation. The tokens are pulled from the allowance given to the
+/// contract, so that any submitter can post the order and pay the fees.
+pub fn swap_order_signed(
+    env: &Env,
+    order: SwapOrder,
+    nonce: u128,
+    deadline: u64,
+) -> Result<(), ContractError> {
+    if env.ledger().timestamp() > deadline {
+        return Err(ContractError::PermitExpired);
+    }
+
+    let contract_address = env.current_contract_address();
+    let creator = Address::from_string(&order.creator());
+    if storage::nonce_used(&env, &creator, nonce) {
+        return Err(ContractError::InvalidNonce);
+    }
+
+    let permit_hash = helpers::permit_hash(&env, &order, nonce, deadline)?;
+    creator.require_auth_for_args((permit_hash,).into_val(env));
+    storage::store_used_nonce(&env, &creator, nonce);
+
+    helpers::ensure_network_supported(&env, &order.dst_nid())?;
+
+    let token = Address::from_string(&order.token());
+    helpers::transfer_token_from(&env, &token, &creator, &contract_address, order.amount());
+
+    open_order(&env, order)
+}
+
+fn open_order(env: &Env, order: SwapOrder) -> Result<(), ContractError> {
+    let mut order = order;
+    let contract_address = env.current_contract_address();
+
     let deposit_id = storage::increment_deposit_id(&env);
     order.set_id(deposit_id);
     order.set_src_nid(storage::nid(&env)?);
     order.set_emitter(contract_address.to_string());
 
     storage::store_order(&env, deposit_id, &order);
+    storage::store_order_status(&env, deposit_id, &OrderStatus::new(&env));
     event::swap_intent(
         &env,
         order.id(),
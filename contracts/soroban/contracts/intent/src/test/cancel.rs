@@ -195,3 +195,43 @@ fn test_resolve_cancel_for_already_filled_order() {
     let res = client.recv_message(&src_network, &conn_sn, &msg.encode(&ctx.env));
     assert_eq!(res, ());
 }
+
+#[test]
+fn test_resolve_cancel_for_partially_filled_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    let order_hash = helpers::hash_data(&ctx.env, &order.encode(&ctx.env));
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_pending_fill(&ctx.env, &order_hash, 40);
+    });
+
+    let cancel = Cancel::new(order.encode(&ctx.env));
+    let msg = OrderMessage::new(MessageType::CANCEL, cancel.encode(&ctx.env));
+    client.recv_message(&order.src_nid(), &1, &msg.encode(&ctx.env));
+
+    let fill = OrderFill::new_partial(order.id(), order.encode(&ctx.env), order.creator(), 40);
+    let refund = OrderMessage::new(MessageType::FILL, fill.encode(&ctx.env));
+    let message_event = Message {
+        targetNetwork: order.src_nid(),
+        sn: 1,
+        msg: refund.encode(&ctx.env),
+    };
+    let events = ctx.env.events().all();
+    assert_eq!(
+        vec![&ctx.env, events.get_unchecked(events.len() - 2)],
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("Message",).into_val(&ctx.env),
+                message_event.into_val(&ctx.env)
+            )
+        ]
+    );
+
+    assert_eq!(client.get_finished_order(&order_hash), true);
+    assert_eq!(client.get_pending_fill(&order_hash), None);
+}
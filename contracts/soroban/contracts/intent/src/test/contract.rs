@@ -1,11 +1,16 @@
 use soroban_sdk::{
+    bytes,
     testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
     Address, IntoVal, Symbol,
 };
 
 extern crate std;
 use super::setup::TestContext;
-use crate::contract::IntentClient;
+use crate::{
+    contract::IntentClient,
+    error::ContractError,
+    types::{MessageType, OrderMessage},
+};
 
 mod intent {
     soroban_sdk::contractimport!(file = "../../target/wasm32-unknown-unknown/release/intent.wasm");
@@ -155,6 +160,34 @@ fn test_get_receipt() {
     assert_eq!(res, false)
 }
 
+#[test]
+fn test_recv_message_with_malformed_message() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let cases = [
+        (bytes!(&ctx.env, 0xc5010203), ContractError::DecodeFailed),
+        (bytes!(&ctx.env, 0xc101), ContractError::InvalidListLength),
+        (
+            bytes!(&ctx.env, 0xc20980),
+            ContractError::InvalidMessageType,
+        ),
+        (
+            OrderMessage::new(MessageType::FILL, bytes!(&ctx.env, 0xc101)).encode(&ctx.env),
+            ContractError::InvalidListLength,
+        ),
+        (
+            OrderMessage::new(MessageType::CANCEL, bytes!(&ctx.env, 0x01)).encode(&ctx.env),
+            ContractError::DecodeFailed,
+        ),
+    ];
+    for (conn_sn, (msg, error)) in cases.into_iter().enumerate() {
+        let res = client.try_recv_message(&ctx.dst_nid, &(conn_sn as u128), &msg);
+        assert_eq!(res, Err(Ok(error)));
+    }
+}
+
 #[test]
 fn test_upgrade() {
     let ctx = TestContext::default();
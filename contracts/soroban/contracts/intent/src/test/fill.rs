@@ -10,7 +10,7 @@ use crate::{
     contract::IntentClient,
     event::{Message, OrderFilled},
     helpers, storage,
-    types::{MessageType, OrderFill, OrderMessage},
+    types::{MessageType, OrderFill, OrderMessage, SwapOrder},
 };
 
 #[test]
@@ -214,3 +214,152 @@ fn test_resolve_fill_with_invalid_network_id() {
     let receipt = client.get_receipt(&src_network, &conn_sn);
     assert_eq!(receipt, true)
 }
+
+#[test]
+fn test_partial_fill_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    let dst_address = Address::from_string(&order.dst_address());
+    let solver_address = Address::generate(&ctx.env);
+    ctx.mint_native_token(&ctx.solver, 1000);
+
+    client.partial_fill(&order, &ctx.solver, &solver_address.to_string(), &40);
+
+    let fill = OrderFill::new_partial(
+        order.id(),
+        order.encode(&ctx.env),
+        solver_address.to_string(),
+        40,
+    );
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode(&ctx.env));
+    let message_event = Message {
+        targetNetwork: order.src_nid(),
+        sn: 1,
+        msg: msg.encode(&ctx.env),
+    };
+    let events = ctx.env.events().all();
+    assert_eq!(
+        vec![&ctx.env, events.get_unchecked(events.len() - 2)],
+        vec![
+            &ctx.env,
+            (
+                client.address.clone(),
+                ("Message",).into_val(&ctx.env),
+                message_event.into_val(&ctx.env)
+            )
+        ]
+    );
+
+    let order_hash = helpers::hash_data(&ctx.env, &order.encode(&ctx.env));
+    assert_eq!(client.get_finished_order(&order_hash), false);
+    assert_eq!(client.get_pending_fill(&order_hash), Some(60));
+
+    client.fill(&order, &ctx.solver, &solver_address.to_string());
+
+    assert_eq!(client.get_finished_order(&order_hash), true);
+    assert_eq!(client.get_pending_fill(&order_hash), None);
+
+    let protocol_fee = client.get_protocol_fee();
+    let fee = (40 * protocol_fee) / 10_000 + (60 * protocol_fee) / 10_000;
+    assert_eq!(ctx.get_native_token_balance(&ctx.fee_handler), fee);
+    assert_eq!(
+        ctx.get_native_token_balance(&dst_address),
+        order.to_amount() - fee
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_partial_fill_exceeding_remaining_amount() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    let solver_address = Address::generate(&ctx.env);
+    ctx.mint_native_token(&ctx.solver, 1000);
+
+    client.partial_fill(&order, &ctx.solver, &solver_address.to_string(), &60);
+    client.partial_fill(&order, &ctx.solver, &solver_address.to_string(), &60);
+}
+
+#[test]
+fn test_resolve_partial_fills() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let first_solver = Address::generate(&ctx.env);
+    let second_solver = Address::generate(&ctx.env);
+    ctx.mint_native_token(&ctx.solver, 1000);
+    ctx.mint_native_token(&ctx.contract, 100);
+
+    let order = ctx.get_dummy_swap(ctx.nid.clone());
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_order(&ctx.env, order.id(), &order);
+    });
+
+    client.partial_fill(&order, &ctx.solver, &first_solver.to_string(), &30);
+
+    assert_eq!(ctx.get_native_token_balance(&first_solver), 30);
+    assert_eq!(ctx.get_native_token_balance(&ctx.contract), 70);
+    assert_eq!(client.get_order(&order.id()), order);
+
+    client.fill(&order, &ctx.solver, &second_solver.to_string());
+
+    assert_eq!(ctx.get_native_token_balance(&second_solver), 70);
+    assert_eq!(ctx.get_native_token_balance(&ctx.contract), 0);
+    ctx.env.as_contract(&ctx.contract, || {
+        let res = storage::get_order(&ctx.env, order.id());
+        assert_eq!(res.is_err(), true);
+        assert_eq!(storage::pending_order_amount(&ctx.env, order.id()), None);
+    });
+}
+
+#[test]
+fn test_resolve_partial_fill_releases_proportional_amount() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let solver_address = Address::generate(&ctx.env);
+    ctx.mint_native_token(&ctx.contract, 100);
+
+    let order = SwapOrder::new(
+        1,
+        ctx.contract.to_string(),
+        ctx.nid.clone(),
+        ctx.dst_nid.clone(),
+        Address::generate(&ctx.env).to_string(),
+        Address::generate(&ctx.env).to_string(),
+        ctx.native_token.to_string(),
+        100,
+        ctx.native_token.to_string(),
+        300,
+        bytes!(&ctx.env, 0x00),
+    );
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_order(&ctx.env, order.id(), &order);
+    });
+
+    for conn_sn in 1..=3 {
+        let fill = OrderFill::new_partial(
+            order.id(),
+            order.encode(&ctx.env),
+            solver_address.to_string(),
+            100,
+        );
+        let msg = OrderMessage::new(MessageType::FILL, fill.encode(&ctx.env));
+        client.recv_message(&ctx.dst_nid, &conn_sn, &msg.encode(&ctx.env));
+    }
+
+    // 100 * 100 / 300 rounds down, the last tranche releases the remainder
+    assert_eq!(ctx.get_native_token_balance(&solver_address), 100);
+    assert_eq!(ctx.get_native_token_balance(&ctx.contract), 0);
+    ctx.env.as_contract(&ctx.contract, || {
+        assert_eq!(storage::get_order(&ctx.env, order.id()).is_err(), true);
+    });
+}
@@ -1,6 +1,9 @@
 pub mod cancel;
 pub mod contract;
 pub mod fill;
+pub mod network;
 pub mod setup;
+pub mod status;
 pub mod swap;
+pub mod ttl;
 pub mod types;
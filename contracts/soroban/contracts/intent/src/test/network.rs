@@ -0,0 +1,270 @@
+use soroban_sdk::{
+    testutils::{AuthorizedFunction, AuthorizedInvocation, Events},
+    vec, Address, IntoVal, String, Symbol,
+};
+
+extern crate std;
+use super::setup::TestContext;
+use crate::{
+    contract::IntentClient,
+    event::NetworkUpdated,
+    helpers,
+    types::{AddressFormat, NetworkConfig, StorageKey},
+};
+
+#[test]
+fn test_set_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let nid = String::from_str(&ctx.env, "0x1.icon");
+    let config = NetworkConfig::new(true, AddressFormat::Other, false);
+    client.set_network(&nid, &config);
+
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            ctx.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&ctx.env, "set_network"),
+                    (nid.clone(), config.clone()).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    let event = NetworkUpdated {
+        nid: nid.clone(),
+        enabled: true,
+        addressFormat: AddressFormat::Other,
+        partialFills: false,
+    };
+    assert_eq!(
+        vec![&ctx.env, ctx.env.events().all().last_unchecked()],
+        vec![
+            &ctx.env,
+            (
+                ctx.contract.clone(),
+                ("NetworkUpdated",).into_val(&ctx.env),
+                event.into_val(&ctx.env)
+            )
+        ]
+    );
+
+    assert_eq!(client.get_network(&nid), config);
+    assert_eq!(client.get_networks(), vec![&ctx.env, nid]);
+}
+
+#[test]
+fn test_update_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let config = NetworkConfig::new(false, AddressFormat::Base58, false);
+    client.set_network(&ctx.dst_nid, &config);
+
+    assert_eq!(client.get_network(&ctx.dst_nid), config);
+    assert_eq!(client.get_networks(), vec![&ctx.env, ctx.dst_nid.clone()]);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_get_unknown_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    client.get_network(&String::from_str(&ctx.env, "sui"));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_swap_to_unknown_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let order = ctx.get_dummy_swap(String::from_str(&ctx.env, "solanna"));
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+
+    client.swap(&order);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_swap_on_new_contract_without_networks() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+
+    client.swap(&order);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_swap_to_disabled_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    client.set_network(
+        &ctx.dst_nid,
+        &NetworkConfig::new(false, AddressFormat::Base58, false),
+    );
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+
+    client.swap(&order);
+}
+
+#[test]
+fn test_swap_on_same_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.nid.clone());
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+
+    client.swap(&order);
+
+    assert_eq!(client.get_deposit_id(), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_fill_from_unknown_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let mut order = ctx.get_dummy_swap(ctx.nid.clone());
+    order.set_src_nid(String::from_str(&ctx.env, "sui"));
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+
+    client.fill(&order, &ctx.solver, &ctx.solver.to_string());
+}
+
+/// Sets up a contract that does not enforce its empty network registry, as left by an upgrade
+/// from a version without the registry
+fn init_upgraded_context(ctx: &TestContext, client: &IntentClient) {
+    ctx.env.mock_all_auths();
+
+    client.initialize(
+        &ctx.nid,
+        &ctx.admin,
+        &ctx.fee_handler,
+        &ctx.upgrade_authority,
+    );
+    client.set_protocol_fee(&100);
+
+    // The contract was initialized by the version without the network registry
+    ctx.env.as_contract(&ctx.contract, || {
+        ctx.env
+            .storage()
+            .instance()
+            .remove(&StorageKey::NetworkRegistry);
+    });
+}
+
+#[test]
+fn test_swap_and_fill_on_upgraded_contract() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    init_upgraded_context(&ctx, &client);
+
+    assert_eq!(client.get_networks().len(), 0);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+    client.swap(&order);
+
+    let mut order = ctx.get_dummy_swap(ctx.nid.clone());
+    order.set_src_nid(String::from_str(&ctx.env, "sui"));
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+    client.fill(&order, &ctx.solver, &ctx.solver.to_string());
+
+    assert_eq!(client.get_deposit_id(), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_swap_on_upgraded_contract_after_registering_networks() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    init_upgraded_context(&ctx, &client);
+
+    client.set_network(
+        &ctx.dst_nid,
+        &NetworkConfig::new(true, AddressFormat::Base58, false),
+    );
+
+    let order = ctx.get_dummy_swap(String::from_str(&ctx.env, "sui"));
+    ctx.mint_native_token(&Address::from_string(&order.creator()), order.amount());
+    client.swap(&order);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #17)")]
+fn test_partial_fill_to_network_without_partial_fills() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let mut order = ctx.get_dummy_swap(ctx.nid.clone());
+    order.set_src_nid(ctx.dst_nid.clone());
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+
+    client.partial_fill(&order, &ctx.solver, &ctx.solver.to_string(), &40);
+}
+
+#[test]
+fn test_partial_fill_to_network_with_partial_fills() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let src_nid = String::from_str(&ctx.env, "stellar-testnet");
+    client.set_network(
+        &src_nid,
+        &NetworkConfig::new(true, AddressFormat::Strkey, true),
+    );
+
+    let mut order = ctx.get_dummy_swap(ctx.nid.clone());
+    order.set_src_nid(src_nid);
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+
+    client.partial_fill(&order, &ctx.solver, &ctx.solver.to_string(), &40);
+
+    let order_hash = helpers::hash_data(&ctx.env, &order.encode(&ctx.env));
+    assert_eq!(client.get_pending_fill(&order_hash), Some(60));
+}
+
+#[test]
+fn test_complete_fill_to_network_without_partial_fills() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let mut order = ctx.get_dummy_swap(ctx.nid.clone());
+    order.set_src_nid(ctx.dst_nid.clone());
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+
+    client.partial_fill(
+        &order,
+        &ctx.solver,
+        &ctx.solver.to_string(),
+        &order.to_amount(),
+    );
+
+    let order_hash = helpers::hash_data(&ctx.env, &order.encode(&ctx.env));
+    assert_eq!(client.get_finished_order(&order_hash), true);
+}
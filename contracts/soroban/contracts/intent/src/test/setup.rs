@@ -4,7 +4,7 @@ use soroban_sdk::{bytes, testutils::Address as _, token, Address, Env, String};
 
 use crate::{
     contract::{Intent, IntentClient},
-    types::SwapOrder,
+    types::{AddressFormat, NetworkConfig, SwapOrder},
 };
 
 pub mod intent {}
@@ -54,6 +54,16 @@ impl TestContext {
         client.set_protocol_fee(&100);
     }
 
+    /// Initializes the contract with `dst_nid` registered as a supported network
+    pub fn init_context_with_networks(&self, client: &IntentClient<'_>) {
+        self.init_context(client);
+
+        client.set_network(
+            &self.dst_nid,
+            &NetworkConfig::new(true, AddressFormat::Base58, false),
+        );
+    }
+
     pub fn get_dummy_swap(&self, dst_nid: String) -> SwapOrder {
         SwapOrder::new(
             1,
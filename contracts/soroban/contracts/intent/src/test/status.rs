@@ -0,0 +1,201 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+use super::setup::TestContext;
+use crate::{
+    contract::IntentClient,
+    storage,
+    types::{MessageType, OrderFill, OrderMessage, OrderState, SwapOrder},
+};
+
+fn swap(ctx: &TestContext, client: &IntentClient<'_>, order: &SwapOrder) -> SwapOrder {
+    let creator = Address::from_string(&order.creator());
+    ctx.mint_native_token(&creator, order.amount());
+    client.swap(order);
+
+    client.get_order(&client.get_deposit_id())
+}
+
+fn recv_fill(ctx: &TestContext, client: &IntentClient<'_>, conn_sn: u128, fill: OrderFill) {
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode(&ctx.env));
+    client.recv_message(&ctx.dst_nid, &conn_sn, &msg.encode(&ctx.env));
+}
+
+#[test]
+fn test_swap_opens_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+    ctx.env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.dst_nid.clone()));
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Open);
+    assert_eq!(status.solver(), None);
+    assert_eq!(status.created_at(), 1000);
+    assert_eq!(status.updated_at(), 1000);
+}
+
+#[test]
+fn test_partial_fills_fill_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+    ctx.env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.dst_nid.clone()));
+    let solver = Address::generate(&ctx.env).to_string();
+
+    ctx.env.ledger().with_mut(|l| l.timestamp = 2000);
+    let fill = OrderFill::new_partial(order.id(), order.encode(&ctx.env), solver.clone(), 40);
+    recv_fill(&ctx, &client, 1, fill);
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::PartiallyFilled);
+    assert_eq!(status.solver(), Some(solver.clone()));
+    assert_eq!(status.updated_at(), 2000);
+
+    ctx.env.ledger().with_mut(|l| l.timestamp = 3000);
+    let fill = OrderFill::new_partial(order.id(), order.encode(&ctx.env), solver.clone(), 60);
+    recv_fill(&ctx, &client, 2, fill);
+
+    // The status remains after the order is removed
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Filled);
+    assert_eq!(status.solver(), Some(solver));
+    assert_eq!(status.created_at(), 1000);
+    assert_eq!(status.updated_at(), 3000);
+    assert!(client.try_get_order(&order.id()).is_err());
+}
+
+#[test]
+fn test_cancel_refunds_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.dst_nid.clone()));
+
+    client.cancel(&order.id());
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::CancelRequested);
+
+    // The destination network confirms the cancel with a fill paying the creator
+    let fill = OrderFill::new(order.id(), order.encode(&ctx.env), order.creator());
+    recv_fill(&ctx, &client, 1, fill);
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Refunded);
+    assert_eq!(status.solver(), Some(order.creator()));
+}
+
+#[test]
+fn test_cancel_refunds_partially_filled_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.dst_nid.clone()));
+    let solver = Address::generate(&ctx.env).to_string();
+
+    let fill = OrderFill::new_partial(order.id(), order.encode(&ctx.env), solver, 40);
+    recv_fill(&ctx, &client, 1, fill);
+    client.cancel(&order.id());
+
+    // The destination network refunds the part of the order that is still open
+    let fill = OrderFill::new_partial(order.id(), order.encode(&ctx.env), order.creator(), 60);
+    recv_fill(&ctx, &client, 2, fill);
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Refunded);
+    assert_eq!(status.solver(), Some(order.creator()));
+    assert!(client.try_get_order(&order.id()).is_err());
+}
+
+#[test]
+fn test_same_chain_cancel_refunds_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.nid.clone()));
+
+    // The order is refunded once the fill sent by the cancel is resolved
+    client.cancel(&order.id());
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::CancelRequested);
+
+    let fill = OrderFill::new(order.id(), order.encode(&ctx.env), order.creator());
+    let msg = OrderMessage::new(MessageType::FILL, fill.encode(&ctx.env));
+    client.recv_message(&ctx.nid, &1, &msg.encode(&ctx.env));
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Refunded);
+    assert_eq!(status.solver(), Some(order.creator()));
+    assert!(client.try_get_order(&order.id()).is_err());
+}
+
+#[test]
+fn test_fill_after_cancel_request_fills_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.dst_nid.clone()));
+    client.cancel(&order.id());
+
+    // A solver filled the order before the cancel reached the destination network
+    let solver = Address::generate(&ctx.env).to_string();
+    let fill = OrderFill::new(order.id(), order.encode(&ctx.env), solver.clone());
+    recv_fill(&ctx, &client, 1, fill);
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Filled);
+    assert_eq!(status.solver(), Some(solver));
+}
+
+#[test]
+fn test_same_chain_fill_fills_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = swap(&ctx, &client, &ctx.get_dummy_swap(ctx.nid.clone()));
+    let solver = Address::generate(&ctx.env).to_string();
+    ctx.mint_native_token(&ctx.solver, order.to_amount());
+
+    client.fill(&order, &ctx.solver, &solver);
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Filled);
+    assert_eq!(status.solver(), Some(solver));
+}
+
+#[test]
+fn test_order_status_of_order_without_status() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.env.as_contract(&ctx.contract, || {
+        storage::store_order(&ctx.env, order.id(), &order);
+    });
+
+    let status = client.get_order_status(&order.id());
+    assert_eq!(status.state(), OrderState::Open);
+    assert_eq!(status.created_at(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_order_status_of_unknown_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    client.get_order_status(&1);
+}
@@ -1,19 +1,26 @@
 use soroban_sdk::{
     bytes, symbol_short,
-    testutils::{AuthorizedFunction, AuthorizedInvocation, Events},
-    vec, IntoVal, String,
+    testutils::{
+        AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MockAuth, MockAuthInvoke,
+    },
+    token, vec, Address, BytesN, IntoVal, String, Symbol,
 };
 
 extern crate std;
 use super::setup::TestContext;
-use crate::{contract::IntentClient, event::SwapIntent, types::SwapOrder};
+use crate::{
+    contract::IntentClient,
+    event::SwapIntent,
+    helpers,
+    types::{OrderState, SwapOrder},
+};
 
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #10)")]
 fn test_swap_with_invalid_emitter_address() {
     let ctx = TestContext::default();
     let client = IntentClient::new(&ctx.env, &ctx.contract);
-    ctx.init_context(&client);
+    ctx.init_context_with_networks(&client);
 
     let order = SwapOrder::new(
         1,
@@ -37,7 +44,7 @@ fn test_swap_with_invalid_emitter_address() {
 fn test_swap_with_insufficient_balance() {
     let ctx = TestContext::default();
     let client = IntentClient::new(&ctx.env, &ctx.contract);
-    ctx.init_context(&client);
+    ctx.init_context_with_networks(&client);
 
     let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
 
@@ -48,7 +55,7 @@ fn test_swap_with_insufficient_balance() {
 fn test_swap() {
     let ctx = TestContext::default();
     let client = IntentClient::new(&ctx.env, &ctx.contract);
-    ctx.init_context(&client);
+    ctx.init_context_with_networks(&client);
     ctx.mint_native_token(&ctx.admin, 100);
 
     let order = SwapOrder::new(
@@ -126,3 +133,133 @@ fn test_swap() {
     assert_eq!(contract_balance, 100);
     assert_eq!(creator_balance, 0);
 }
+
+/// Returns an order whose creator approved the contract to pull its amount
+fn signed_swap_order(ctx: &TestContext, dst_nid: String) -> (Address, SwapOrder) {
+    let order = ctx.get_dummy_swap(dst_nid);
+    let creator = Address::from_string(&order.creator());
+
+    ctx.mint_native_token(&creator, order.amount());
+    token::TokenClient::new(&ctx.env, &ctx.native_token).approve(
+        &creator,
+        &ctx.contract,
+        &(order.amount() as i128),
+        &(ctx.env.ledger().sequence() + 100),
+    );
+
+    (creator, order)
+}
+
+fn permit_hash(ctx: &TestContext, order: &SwapOrder, nonce: u128, deadline: u64) -> BytesN<32> {
+    ctx.env.as_contract(&ctx.contract, || {
+        helpers::permit_hash(&ctx.env, order, nonce, deadline).unwrap()
+    })
+}
+
+fn mock_permit(ctx: &TestContext, creator: &Address, permit_hash: &BytesN<32>) {
+    ctx.env.mock_auths(&[MockAuth {
+        address: creator,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract,
+            fn_name: "swap_signed",
+            args: (permit_hash.clone(),).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+#[test]
+fn test_swap_signed() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let (creator, order) = signed_swap_order(&ctx, ctx.dst_nid.clone());
+    let permit_hash = permit_hash(&ctx, &order, 1, 1000);
+
+    // The creator only authorizes the permit, the tokens come from the allowance
+    mock_permit(&ctx, &creator, &permit_hash);
+    client.swap_signed(&order, &1, &1000);
+
+    assert_eq!(
+        ctx.env.auths(),
+        std::vec![(
+            creator.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    ctx.contract.clone(),
+                    Symbol::new(&ctx.env, "swap_signed"),
+                    (permit_hash,).into_val(&ctx.env)
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    let id = client.get_deposit_id();
+    assert_eq!(client.get_order(&id).creator(), creator.to_string());
+    assert_eq!(client.get_order_status(&id).state(), OrderState::Open);
+    assert_eq!(ctx.get_native_token_balance(&creator), 0);
+    assert_eq!(ctx.get_native_token_balance(&ctx.contract), order.amount());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_swap_signed_with_permit_of_other_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let (creator, order) = signed_swap_order(&ctx, ctx.dst_nid.clone());
+    let permit_hash = permit_hash(&ctx, &order, 1, 1000);
+    mock_permit(&ctx, &creator, &permit_hash);
+
+    let mut tampered = order.clone();
+    tampered.set_data(bytes!(&ctx.env, 0x01));
+    client.swap_signed(&tampered, &1, &1000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_swap_signed_with_used_nonce() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let (creator, order) = signed_swap_order(&ctx, ctx.dst_nid.clone());
+    ctx.mint_native_token(&creator, order.amount());
+    token::TokenClient::new(&ctx.env, &ctx.native_token).approve(
+        &creator,
+        &ctx.contract,
+        &(2 * order.amount() as i128),
+        &(ctx.env.ledger().sequence() + 100),
+    );
+
+    client.swap_signed(&order, &1, &1000);
+    client.swap_signed(&order, &1, &1000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_swap_signed_after_deadline() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let (_, order) = signed_swap_order(&ctx, ctx.dst_nid.clone());
+    ctx.env.ledger().with_mut(|l| l.timestamp = 1001);
+
+    client.swap_signed(&order, &1, &1000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_swap_signed_to_unknown_network() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let (_, order) = signed_swap_order(&ctx, String::from_str(&ctx.env, "solanna"));
+
+    client.swap_signed(&order, &1, &1000);
+}
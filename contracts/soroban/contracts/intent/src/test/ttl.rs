@@ -0,0 +1,201 @@
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Ledger},
+    xdr::ScAddress,
+    Address, BytesN, Env, TryFromVal,
+};
+
+extern crate std;
+use super::setup::TestContext;
+use crate::{
+    contract::{Intent, IntentClient},
+    storage::{DAY_IN_LEDGERS, LEDGER_BUMP_ORDER, LEDGER_BUMP_REPLAY},
+    types::storage_types::StorageKey,
+};
+
+fn get_ttl(env: &Env, contract: &Address, key: &StorageKey) -> u32 {
+    env.as_contract(contract, || env.storage().persistent().get_ttl(key))
+}
+
+/// Advances the ledger by `days`, keeping the contract instance alive with admin calls
+fn advance_days(ctx: &TestContext, client: &IntentClient<'_>, days: u32) {
+    for _ in 0..days / 29 {
+        ctx.env
+            .ledger()
+            .with_mut(|l| l.sequence_number += DAY_IN_LEDGERS * 29);
+        client.set_protocol_fee(&100);
+    }
+    ctx.env
+        .ledger()
+        .with_mut(|l| l.sequence_number += DAY_IN_LEDGERS * (days % 29));
+}
+
+fn swap(ctx: &TestContext, client: &IntentClient<'_>) -> u128 {
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    let creator = Address::from_string(&order.creator());
+    ctx.mint_native_token(&creator, order.amount());
+    client.swap(&order);
+
+    client.get_deposit_id()
+}
+
+#[test]
+fn test_swap_extends_order_ttl() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let id = swap(&ctx, &client);
+
+    let ttl = get_ttl(&ctx.env, &ctx.contract, &StorageKey::Orders(id));
+    assert_eq!(ttl, LEDGER_BUMP_ORDER);
+}
+
+#[test]
+fn test_finished_order_ttl_outlives_order_ttl() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.mint_native_token(&ctx.solver, 1000);
+    client.fill(&order, &ctx.solver, &ctx.solver.to_string());
+
+    let order_hash = ctx.env.as_contract(&ctx.contract, || {
+        crate::helpers::hash_data(&ctx.env, &order.encode(&ctx.env))
+    });
+    let ttl = get_ttl(
+        &ctx.env,
+        &ctx.contract,
+        &StorageKey::FinishedOrders(order_hash),
+    );
+    assert_eq!(ttl, LEDGER_BUMP_REPLAY);
+    assert!(LEDGER_BUMP_REPLAY > LEDGER_BUMP_ORDER);
+}
+
+#[test]
+fn test_extend_order_ttl() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let id = swap(&ctx, &client);
+
+    // Anyone keeps the order alive past its original expiry
+    for _ in 0..4 {
+        advance_days(&ctx, &client, 29);
+        client.extend_order_ttl(&id);
+    }
+
+    assert_eq!(client.get_order(&id).id(), id);
+    assert_eq!(
+        get_ttl(&ctx.env, &ctx.contract, &StorageKey::Orders(id)),
+        LEDGER_BUMP_ORDER - DAY_IN_LEDGERS * 29
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_extend_order_ttl_for_unknown_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    client.extend_order_ttl(&1);
+}
+
+#[test]
+fn test_extend_fill_ttl() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    let order = ctx.get_dummy_swap(ctx.dst_nid.clone());
+    ctx.mint_native_token(&ctx.solver, 1000);
+    client.partial_fill(&order, &ctx.solver, &ctx.solver.to_string(), &40);
+
+    let order_hash = ctx.env.as_contract(&ctx.contract, || {
+        crate::helpers::hash_data(&ctx.env, &order.encode(&ctx.env))
+    });
+    let key = StorageKey::PendingFills(order_hash.clone());
+    assert_eq!(get_ttl(&ctx.env, &ctx.contract, &key), LEDGER_BUMP_ORDER);
+
+    // The remaining amount stays fillable past its original expiry
+    for _ in 0..4 {
+        advance_days(&ctx, &client, 29);
+        client.extend_fill_ttl(&order_hash);
+    }
+
+    assert_eq!(client.get_pending_fill(&order_hash), Some(60));
+    assert_eq!(
+        get_ttl(&ctx.env, &ctx.contract, &key),
+        LEDGER_BUMP_ORDER - DAY_IN_LEDGERS * 29
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_extend_fill_ttl_for_unknown_fill() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context(&client);
+
+    client.extend_fill_ttl(&BytesN::from_array(&ctx.env, &[0; 32]));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Storage, InternalError)")]
+fn test_get_archived_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let id = swap(&ctx, &client);
+    advance_days(&ctx, &client, 91);
+
+    client.get_order(&id);
+}
+
+#[test]
+fn test_restore_archived_order() {
+    let ctx = TestContext::default();
+    let client = IntentClient::new(&ctx.env, &ctx.contract);
+    ctx.init_context_with_networks(&client);
+
+    let id = swap(&ctx, &client);
+    let order = client.get_order(&id);
+    ctx.env
+        .ledger()
+        .with_mut(|l| l.sequence_number += LEDGER_BUMP_ORDER + 1);
+
+    // Restoring the archived entries gives them the minimum persistent TTL
+    let mut snapshot = ctx.env.to_ledger_snapshot();
+    let sequence = snapshot.sequence_number;
+    let restored_ttl = snapshot.min_persistent_entry_ttl - 1;
+    for (_, (_, live_until)) in snapshot.ledger_entries.iter_mut() {
+        if matches!(live_until, Some(ledger) if *ledger < sequence) {
+            *live_until = Some(sequence + restored_ttl);
+        }
+    }
+
+    let env = Env::from_ledger_snapshot(snapshot);
+    let contract = Address::try_from_val(&env, &ScAddress::from(&ctx.contract)).unwrap();
+    env.register_contract(&contract, Intent);
+    let client = IntentClient::new(&env, &contract);
+
+    assert_eq!(
+        get_ttl(&env, &contract, &StorageKey::Orders(id)),
+        restored_ttl
+    );
+
+    client.extend_order_ttl(&id);
+
+    let restored = client.get_order(&id);
+    assert_eq!(
+        (restored.id(), restored.amount(), restored.to_amount()),
+        (order.id(), order.amount(), order.to_amount())
+    );
+    assert_eq!(
+        get_ttl(&env, &contract, &StorageKey::Orders(id)),
+        LEDGER_BUMP_ORDER
+    );
+}
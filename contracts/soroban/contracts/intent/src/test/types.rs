@@ -1,6 +1,9 @@
 use soroban_sdk::{bytes, Bytes, Env, String};
 
-use crate::types::{Cancel, MessageType, OrderFill, OrderMessage, SwapOrder};
+use crate::{
+    error::ContractError,
+    types::{Cancel, MessageType, OrderFill, OrderMessage, SwapOrder},
+};
 
 #[test]
 fn test_order_fill_decode_1() {
@@ -102,3 +105,231 @@ fn test_swap_order_decode_2() {
     let expected = bytes!(&env, 0xf8c701963078626536343532643464366336316365653937643388457468657265756d87506f6c79676f6e983078336533366564646436356532333932323265376536379c30786432633632313862383735343537613431623666623739363465a43078313433353533343065383537393132313838623766323032643535303232323438378c033b2e3c9fd0803ce80000009a3078393161343732386235313734383466306636313064653762850218711a00946c449988e2f33302803c93f8287dc1d8cb33848a);
     assert_eq!(swap_order.encode(&env), expected)
 }
+
+#[test]
+fn test_order_fill_partial_encode_decode() {
+    let env = Env::default();
+
+    let data = OrderFill::new_partial(
+        1,
+        bytes!(&env, 0x6c449988e2f33302803c93f8287dc1d8cb33848a),
+        String::from_str(&env, "0xcb0a6bbccfccde6be9f10ae781b9d9b00d6e63"),
+        500,
+    );
+    let decoded = OrderFill::decode(&env, data.encode(&env)).unwrap();
+    assert_eq!(decoded.id(), 1);
+    assert_eq!(decoded.amount(), Some(500));
+
+    let full = OrderFill::new(
+        1,
+        bytes!(&env, 0x6c449988e2f33302803c93f8287dc1d8cb33848a),
+        String::from_str(&env, "0xcb0a6bbccfccde6be9f10ae781b9d9b00d6e63"),
+    );
+    assert_eq!(
+        OrderFill::decode(&env, full.encode(&env)).unwrap().amount(),
+        None
+    );
+}
+
+fn dummy_swap_order(env: &Env) -> SwapOrder {
+    SwapOrder::new(
+        1,
+        String::from_str(env, "0xbe6452d4d6c61cee97d3"),
+        String::from_str(env, "Ethereum"),
+        String::from_str(env, "Polygon"),
+        String::from_str(env, "0x3e36eddd65e239222e7e67"),
+        String::from_str(env, "0xd2c6218b875457a41b6fb7964e"),
+        String::from_str(env, "0x14355340e857912188b7f202d550222487"),
+        1000,
+        String::from_str(env, "0x91a4728b517484f0f610de7b"),
+        900,
+        bytes!(env, 0x6c449988e2f33302803c93f8287dc1d8cb33848a),
+    )
+}
+
+/// Decodes `bytes` as every message type, returning whether any of them succeeded
+fn decode_all(env: &Env, bytes: &Bytes) -> bool {
+    let order = SwapOrder::decode(env, bytes.clone()).is_ok();
+    let fill = OrderFill::decode(env, bytes.clone()).is_ok();
+    let cancel = Cancel::decode(env, bytes.clone()).is_ok();
+    let message = OrderMessage::decode(env, bytes.clone()).is_ok();
+
+    order || fill || cancel || message
+}
+
+#[test]
+fn test_decode_round_trip() {
+    let env = Env::default();
+    let order = dummy_swap_order(&env);
+
+    assert_eq!(
+        SwapOrder::decode(&env, order.encode(&env)),
+        Ok(order.clone())
+    );
+
+    let fill = OrderFill::new_partial(
+        u128::MAX,
+        order.encode(&env),
+        String::from_str(&env, "0xcb0a6bbccfccde6be9f10ae781b9d9b00d6e63"),
+        u128::MAX,
+    );
+    let decoded = OrderFill::decode(&env, fill.encode(&env)).unwrap();
+    assert_eq!(decoded.id(), u128::MAX);
+    assert_eq!(decoded.amount(), Some(u128::MAX));
+    assert_eq!(decoded.order_bytes(), order.encode(&env));
+
+    let cancel = Cancel::new(order.encode(&env));
+    let decoded = Cancel::decode(&env, cancel.encode(&env)).unwrap();
+    assert_eq!(decoded.order_bytes(), order.encode(&env));
+
+    let msg = OrderMessage::new(MessageType::CANCEL, cancel.encode(&env));
+    let decoded = OrderMessage::decode(&env, msg.encode(&env)).unwrap();
+    assert!(matches!(decoded.message_type(), MessageType::CANCEL));
+    assert_eq!(decoded.message(), cancel.encode(&env));
+}
+
+#[test]
+fn test_decode_invalid_list_length() {
+    let env = Env::default();
+
+    // A cancel has a single field, a fill three or four
+    let cancel = Cancel::new(bytes!(&env, 0x6c449988e2f33302803c93f8287dc1d8cb33848a));
+    assert_eq!(
+        OrderFill::decode(&env, cancel.encode(&env)).err(),
+        Some(ContractError::InvalidListLength)
+    );
+    assert_eq!(
+        SwapOrder::decode(&env, cancel.encode(&env)).err(),
+        Some(ContractError::InvalidListLength)
+    );
+    assert_eq!(
+        OrderMessage::decode(&env, bytes!(&env, 0xc0)).err(),
+        Some(ContractError::InvalidListLength)
+    );
+    assert_eq!(
+        Cancel::decode(&env, bytes!(&env, 0xc20102)).err(),
+        Some(ContractError::InvalidListLength)
+    );
+}
+
+#[test]
+fn test_decode_invalid_message_type() {
+    let env = Env::default();
+
+    assert_eq!(
+        OrderMessage::decode(&env, bytes!(&env, 0xc20380)).err(),
+        Some(ContractError::InvalidMessageType)
+    );
+    assert_eq!(
+        OrderMessage::decode(&env, bytes!(&env, 0xc68501000000018080)).err(),
+        Some(ContractError::DecodeFailed)
+    );
+}
+
+#[test]
+fn test_decode_malformed_bytes() {
+    let env = Env::default();
+
+    let malformed = [
+        // empty input
+        Bytes::new(&env),
+        // a string instead of a list
+        bytes!(&env, 0x8401020304),
+        // list longer than the input
+        bytes!(&env, 0xc5010203),
+        // trailing bytes after the list
+        bytes!(&env, 0xc2010203),
+        // item longer than its list
+        bytes!(&env, 0xc2830102),
+        // length of length longer than four bytes
+        bytes!(&env, 0xfd0000000000000001),
+        // truncated length of length
+        bytes!(&env, 0xf9),
+        // list length overflowing u32
+        bytes!(&env, 0xfbffffffff),
+    ];
+    for bytes in malformed.iter() {
+        assert_eq!(
+            OrderMessage::decode(&env, bytes.clone()).err(),
+            Some(ContractError::DecodeFailed)
+        );
+        assert!(!decode_all(&env, bytes));
+    }
+
+    // An amount of seventeen significant bytes overflows u128
+    let fill = bytes!(&env, 0xd4910100000000000000000000000000000000808080);
+    assert_eq!(
+        OrderFill::decode(&env, fill).err(),
+        Some(ContractError::DecodeFailed)
+    );
+}
+
+/// Deterministic xorshift generator, so that a failing input can be reproduced
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        (self.next() % n as u64) as u32
+    }
+}
+
+#[test]
+fn test_decode_random_bytes_does_not_panic() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+
+    for _ in 0..2000 {
+        let len = rng.below(80);
+        let mut bytes = Bytes::new(&env);
+        // Biased towards list prefixes to get past the outer header
+        bytes.push_back(0xc0 + rng.below(64) as u8);
+        for _ in 0..len {
+            bytes.push_back(rng.next() as u8);
+        }
+
+        decode_all(&env, &bytes);
+    }
+}
+
+#[test]
+fn test_decode_mutated_messages_does_not_panic() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let mut rng = Rng(0x2545f4914f6cdd1d);
+
+    let order = dummy_swap_order(&env);
+    let fill = OrderFill::new_partial(
+        1,
+        order.encode(&env),
+        String::from_str(&env, "0xcb0a6bbccfccde6be9f10ae781b9d9b00d6e63"),
+        500,
+    );
+    let messages = [
+        order.encode(&env),
+        fill.encode(&env),
+        Cancel::new(order.encode(&env)).encode(&env),
+        OrderMessage::new(MessageType::FILL, fill.encode(&env)).encode(&env),
+    ];
+
+    for _ in 0..2000 {
+        let mut bytes = messages[rng.below(messages.len() as u32) as usize].clone();
+        match rng.below(3) {
+            0 => {
+                let at = rng.below(bytes.len());
+                bytes.set(at, rng.next() as u8);
+            }
+            1 => bytes = bytes.slice(..rng.below(bytes.len())),
+            _ => bytes.insert(rng.below(bytes.len()), rng.next() as u8),
+        }
+
+        decode_all(&env, &bytes);
+    }
+}
@@ -1,11 +1,16 @@
+pub mod network_config;
 pub mod order_cancel;
 pub mod order_fill;
 pub mod order_message;
+pub mod order_status;
+mod rlp;
 pub mod storage_types;
 pub mod swap_order;
 
+pub use network_config::*;
 pub use order_cancel::*;
 pub use order_fill::*;
 pub use order_message::*;
+pub use order_status::*;
 pub use storage_types::*;
 pub use swap_order::*;
@@ -0,0 +1,52 @@
+use soroban_sdk::contracttype;
+
+/// Encoding of the addresses of a network, a hint for clients building orders
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    /// Hex with a `0x` prefix, such as EVM addresses
+    Hex = 1,
+    /// Base58, such as Solana public keys
+    Base58 = 2,
+    /// Stellar strkey
+    Strkey = 3,
+    /// Bech32, such as Cosmos addresses
+    Bech32 = 4,
+    /// Any other encoding
+    Other = 5,
+}
+
+/// Settings of a network orders can be swapped to
+#[contracttype]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkConfig {
+    /// Whether new swaps and fills are accepted for the network
+    enabled: bool,
+    /// Encoding of the addresses of the network
+    address_format: AddressFormat,
+    /// Whether the network settles partial fills, the orders of other networks are only
+    /// filled completely
+    partial_fills: bool,
+}
+
+impl NetworkConfig {
+    pub fn new(enabled: bool, address_format: AddressFormat, partial_fills: bool) -> Self {
+        Self {
+            enabled,
+            address_format,
+            partial_fills,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn address_format(&self) -> AddressFormat {
+        self.address_format
+    }
+
+    pub fn partial_fills(&self) -> bool {
+        self.partial_fills
+    }
+}
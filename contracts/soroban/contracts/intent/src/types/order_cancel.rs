@@ -1,6 +1,9 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::encoder;
 use soroban_sdk::{contracttype, vec, Bytes, Env, Vec};
 
+use super::rlp;
+use crate::error::ContractError;
+
 #[contracttype]
 #[derive(Debug, Clone)]
 pub struct Cancel {
@@ -25,14 +28,12 @@ impl Cancel {
         encoder::encode_list(&e, list, false)
     }
 
-    pub fn decode(e: &Env, list: Bytes) -> Self {
-        let decoded = decoder::decode_list(&e, list);
-        if decoded.len() != 1 {
-            panic!("Invalid rlp bytes length")
-        }
+    pub fn decode(e: &Env, list: Bytes) -> Result<Self, ContractError> {
+        let decoded = rlp::decode_list(&e, list)?;
+        rlp::expect_len(&decoded, 1)?;
 
-        let order_bytes = decoded.get(0).unwrap();
+        let order_bytes = decoded.get_unchecked(0);
 
-        Self { order_bytes }
+        Ok(Self { order_bytes })
     }
 }
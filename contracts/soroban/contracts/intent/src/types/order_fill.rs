@@ -1,6 +1,9 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::encoder;
 use soroban_sdk::{contracttype, vec, Bytes, Env, String, Vec};
 
+use super::rlp;
+use crate::error::ContractError;
+
 #[contracttype]
 #[derive(Debug, Clone)]
 pub struct OrderFill {
@@ -10,6 +13,8 @@ pub struct OrderFill {
     order_bytes: Bytes,
     /// Address of the solver filling the order
     solver: String,
+    /// Amount of `to_amount` settled by this fill, `None` settles the whole remaining order
+    amount: Option<u128>,
 }
 
 impl OrderFill {
@@ -18,6 +23,16 @@ impl OrderFill {
             id,
             order_bytes,
             solver,
+            amount: None,
+        }
+    }
+
+    pub fn new_partial(id: u128, order_bytes: Bytes, solver: String, amount: u128) -> Self {
+        Self {
+            id,
+            order_bytes,
+            solver,
+            amount: Some(amount),
         }
     }
 
@@ -33,30 +48,42 @@ impl OrderFill {
         self.solver.clone()
     }
 
+    pub fn amount(&self) -> Option<u128> {
+        self.amount
+    }
+
     pub fn encode(&self, e: &Env) -> Bytes {
         let mut list: Vec<Bytes> = vec![&e];
 
         list.push_back(encoder::encode_u128(&e, self.id()));
         list.push_back(encoder::encode(&e, self.order_bytes()));
         list.push_back(encoder::encode_string(&e, self.solver()));
+        if let Some(amount) = self.amount() {
+            list.push_back(encoder::encode_u128(&e, amount));
+        }
 
         encoder::encode_list(&e, list, false)
     }
 
-    pub fn decode(e: &Env, list: Bytes) -> Self {
-        let decoded = decoder::decode_list(&e, list);
-        if decoded.len() != 3 {
-            panic!("Invalid rlp bytes length")
+    pub fn decode(e: &Env, list: Bytes) -> Result<Self, ContractError> {
+        let decoded = rlp::decode_list(&e, list)?;
+        if decoded.len() != 3 && decoded.len() != 4 {
+            return Err(ContractError::InvalidListLength);
         }
 
-        let id = decoder::decode_u128(&e, decoded.get(0).unwrap());
-        let order_bytes = decoded.get(1).unwrap();
-        let solver = decoder::decode_string(&e, decoded.get(2).unwrap());
+        let id = rlp::decode_u128(decoded.get_unchecked(0))?;
+        let order_bytes = decoded.get_unchecked(1);
+        let solver = rlp::decode_string(&e, decoded.get_unchecked(2))?;
+        let amount = match decoded.get(3) {
+            Some(amount) => Some(rlp::decode_u128(amount)?),
+            None => None,
+        };
 
-        Self {
+        Ok(Self {
             id,
             order_bytes,
             solver,
-        }
+            amount,
+        })
     }
 }
@@ -1,6 +1,9 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::encoder;
 use soroban_sdk::{contracttype, vec, Bytes, Env, Vec};
 
+use super::rlp;
+use crate::error::ContractError;
+
 #[contracttype]
 #[derive(Debug, Clone, Copy)]
 pub enum MessageType {
@@ -8,12 +11,14 @@ pub enum MessageType {
     CANCEL = 2,
 }
 
-impl From<u32> for MessageType {
-    fn from(value: u32) -> Self {
+impl TryFrom<u32> for MessageType {
+    type Error = ContractError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
         match value {
-            1 => MessageType::FILL,
-            2 => MessageType::CANCEL,
-            _ => panic!("Invalid message type"),
+            1 => Ok(MessageType::FILL),
+            2 => Ok(MessageType::CANCEL),
+            _ => Err(ContractError::InvalidMessageType),
         }
     }
 }
@@ -61,18 +66,16 @@ impl OrderMessage {
         encoder::encode_list(&e, list, false)
     }
 
-    pub fn decode(e: &Env, list: Bytes) -> Self {
-        let decoded = decoder::decode_list(&e, list);
-        if decoded.len() != 2 {
-            panic!("Invalid rlp bytes lenght")
-        }
+    pub fn decode(e: &Env, list: Bytes) -> Result<Self, ContractError> {
+        let decoded = rlp::decode_list(&e, list)?;
+        rlp::expect_len(&decoded, 2)?;
 
-        let msg_type = decoder::decode_u32(&e, decoded.get(0).unwrap());
-        let message = decoded.get(1).unwrap();
+        let msg_type = rlp::decode_u32(decoded.get_unchecked(0))?;
+        let message = decoded.get_unchecked(1);
 
-        Self {
-            message_type: msg_type.into(),
+        Ok(Self {
+            message_type: msg_type.try_into()?,
             message,
-        }
+        })
     }
 }
@@ -0,0 +1,81 @@
+use soroban_sdk::{contracttype, Env, String};
+
+/// Lifecycle of an order on its source network
+#[contracttype]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    /// The order is locked and waiting for fills
+    Open = 1,
+    /// The creator cancelled the order, waiting for the destination network to confirm
+    CancelRequested = 2,
+    /// Part of the order was filled, the rest is still open
+    PartiallyFilled = 3,
+    /// The cancel was confirmed and the amount still open returned to the creator
+    Refunded = 4,
+    /// The order was completely filled and released to the solvers. Filled and refunded
+    /// orders are closed, there is no separate `Closed` state
+    Filled = 5,
+}
+
+#[contracttype]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderStatus {
+    /// Current state of the order
+    state: OrderState,
+    /// Address paid by the last settlement, the creator for a refund
+    solver: Option<String>,
+    /// Ledger timestamp of the order creation, zero for orders created before statuses
+    /// were tracked
+    created_at: u64,
+    /// Ledger timestamp of the last state change
+    updated_at: u64,
+}
+
+impl OrderStatus {
+    pub fn new(e: &Env) -> Self {
+        let now = e.ledger().timestamp();
+        Self {
+            state: OrderState::Open,
+            solver: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Status of an order created before statuses were tracked
+    pub fn legacy() -> Self {
+        Self {
+            state: OrderState::Open,
+            solver: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    pub fn state(&self) -> OrderState {
+        self.state
+    }
+
+    pub fn solver(&self) -> Option<String> {
+        self.solver.clone()
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> u64 {
+        self.updated_at
+    }
+
+    pub fn set_state(&mut self, e: &Env, state: OrderState) {
+        self.state = state;
+        self.updated_at = e.ledger().timestamp();
+    }
+
+    /// Records a settlement of the order paid to `solver`
+    pub fn settle(&mut self, e: &Env, state: OrderState, solver: String) {
+        self.solver = Some(solver);
+        self.set_state(e, state);
+    }
+}
@@ -0,0 +1,132 @@
+//! Fallible RLP decoding of the relayed messages, reporting malformed input as a
+//! `ContractError` instead of aborting the invocation
+
+use soroban_sdk::{Bytes, Env, String, Vec};
+
+use crate::error::ContractError;
+
+/// Longest string accepted in a message, such as an address or a network id
+const MAX_STRING_LEN: usize = 256;
+
+/// Position of the payload of an RLP item
+struct Header {
+    offset: u32,
+    len: u32,
+    is_list: bool,
+}
+
+impl Header {
+    fn end(&self) -> u32 {
+        self.offset + self.len
+    }
+}
+
+/// Reads the big endian length of `n` bytes following the prefix at `at`
+fn long_len(bytes: &Bytes, at: u32, n: u32) -> Result<u32, ContractError> {
+    if n > 4 {
+        return Err(ContractError::DecodeFailed);
+    }
+
+    let mut len: u32 = 0;
+    for i in 1..=n {
+        let byte = bytes.get(at + i).ok_or(ContractError::DecodeFailed)?;
+        len = (len << 8) | byte as u32;
+    }
+
+    Ok(len)
+}
+
+/// Reads the header of the item at `at`, checking that its payload is within `bytes`
+fn header(bytes: &Bytes, at: u32) -> Result<Header, ContractError> {
+    let prefix = bytes.get(at).ok_or(ContractError::DecodeFailed)? as u32;
+
+    let (len_of_len, len, is_list) = match prefix {
+        0x00..=0x7f => {
+            return Ok(Header {
+                offset: at,
+                len: 1,
+                is_list: false,
+            })
+        }
+        0x80..=0xb7 => (0, prefix - 0x80, false),
+        0xb8..=0xbf => (prefix - 0xb7, long_len(bytes, at, prefix - 0xb7)?, false),
+        0xc0..=0xf7 => (0, prefix - 0xc0, true),
+        _ => (prefix - 0xf7, long_len(bytes, at, prefix - 0xf7)?, true),
+    };
+
+    let offset = at + 1 + len_of_len;
+    match offset.checked_add(len) {
+        Some(end) if end <= bytes.len() => Ok(Header {
+            offset,
+            len,
+            is_list,
+        }),
+        _ => Err(ContractError::DecodeFailed),
+    }
+}
+
+/// Decodes the items of an RLP list spanning all of `bytes`. Strings are returned without
+/// their header, nested lists as they are encoded.
+pub fn decode_list(e: &Env, bytes: Bytes) -> Result<Vec<Bytes>, ContractError> {
+    let list = header(&bytes, 0)?;
+    if !list.is_list || list.end() != bytes.len() {
+        return Err(ContractError::DecodeFailed);
+    }
+
+    let mut items = Vec::new(e);
+    let mut at = list.offset;
+    while at < list.end() {
+        let item = header(&bytes, at)?;
+        if item.end() > list.end() {
+            return Err(ContractError::DecodeFailed);
+        }
+
+        if item.is_list {
+            items.push_back(bytes.slice(at..item.end()));
+        } else {
+            items.push_back(bytes.slice(item.offset..item.end()));
+        }
+        at = item.end();
+    }
+
+    Ok(items)
+}
+
+/// Decodes a big endian unsigned integer, leading zeros being accepted as some encoders
+/// prepend one to keep the value positive
+pub fn decode_u128(bytes: Bytes) -> Result<u128, ContractError> {
+    let mut value: u128 = 0;
+    for byte in bytes.iter() {
+        if value >> 120 != 0 {
+            return Err(ContractError::DecodeFailed);
+        }
+        value = (value << 8) | byte as u128;
+    }
+
+    Ok(value)
+}
+
+pub fn decode_u32(bytes: Bytes) -> Result<u32, ContractError> {
+    u32::try_from(decode_u128(bytes)?).map_err(|_| ContractError::DecodeFailed)
+}
+
+pub fn decode_string(e: &Env, bytes: Bytes) -> Result<String, ContractError> {
+    let len = bytes.len() as usize;
+    if len > MAX_STRING_LEN {
+        return Err(ContractError::DecodeFailed);
+    }
+
+    let mut buf = [0u8; MAX_STRING_LEN];
+    bytes.copy_into_slice(&mut buf[..len]);
+
+    Ok(String::from_bytes(e, &buf[..len]))
+}
+
+/// Checks that a decoded list has the number of fields of the message
+pub fn expect_len(list: &Vec<Bytes>, len: u32) -> Result<(), ContractError> {
+    if list.len() != len {
+        return Err(ContractError::InvalidListLength);
+    }
+
+    Ok(())
+}
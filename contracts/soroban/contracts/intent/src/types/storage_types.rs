@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String};
 
 #[contracttype]
 pub enum StorageKey {
@@ -11,9 +11,14 @@ pub enum StorageKey {
     UpgradeAuthority,
     Version,
     ConnSn,
+    NetworkIds,
+    NetworkRegistry,
+    Networks(String),
     Orders(u128),
+    OrderStatus(u128),
     PendingOrderAmount(u128),
     PendingFills(BytesN<32>),
     FinishedOrders(BytesN<32>),
     Receipts(String, u128),
+    UsedNonces(Address, u128),
 }
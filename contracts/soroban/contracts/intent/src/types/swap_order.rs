@@ -1,6 +1,9 @@
-use soroban_rlp::{decoder, encoder};
+use soroban_rlp::encoder;
 use soroban_sdk::{contracttype, vec, Bytes, BytesN, Env, String, Vec};
 
+use super::rlp;
+use crate::error::ContractError;
+
 #[contracttype]
 #[derive(Debug, Clone, PartialEq)]
 pub struct SwapOrder {
@@ -139,25 +142,23 @@ impl SwapOrder {
         encoder::encode_list(&e, bytes, false)
     }
 
-    pub fn decode(e: &Env, bytes: Bytes) -> Self {
-        let decoded = decoder::decode_list(&e, bytes);
-        if decoded.len() != 11 {
-            panic!("Invalid rlp bytes")
-        }
+    pub fn decode(e: &Env, bytes: Bytes) -> Result<Self, ContractError> {
+        let decoded = rlp::decode_list(&e, bytes)?;
+        rlp::expect_len(&decoded, 11)?;
 
-        let id = decoder::decode_u128(&e, decoded.get(0).unwrap());
-        let emitter = decoder::decode_string(&e, decoded.get(1).unwrap());
-        let src_nid = decoder::decode_string(&e, decoded.get(2).unwrap());
-        let dst_nid = decoder::decode_string(&e, decoded.get(3).unwrap());
-        let creator = decoder::decode_string(&e, decoded.get(4).unwrap());
-        let destination_address = decoder::decode_string(&e, decoded.get(5).unwrap());
-        let token = decoder::decode_string(&e, decoded.get(6).unwrap());
-        let amount = decoder::decode_u128(&e, decoded.get(7).unwrap());
-        let to_token = decoder::decode_string(&e, decoded.get(8).unwrap());
-        let to_amount = decoder::decode_u128(&e, decoded.get(9).unwrap());
-        let data = decoded.get(10).unwrap();
+        let id = rlp::decode_u128(decoded.get_unchecked(0))?;
+        let emitter = rlp::decode_string(&e, decoded.get_unchecked(1))?;
+        let src_nid = rlp::decode_string(&e, decoded.get_unchecked(2))?;
+        let dst_nid = rlp::decode_string(&e, decoded.get_unchecked(3))?;
+        let creator = rlp::decode_string(&e, decoded.get_unchecked(4))?;
+        let destination_address = rlp::decode_string(&e, decoded.get_unchecked(5))?;
+        let token = rlp::decode_string(&e, decoded.get_unchecked(6))?;
+        let amount = rlp::decode_u128(decoded.get_unchecked(7))?;
+        let to_token = rlp::decode_string(&e, decoded.get_unchecked(8))?;
+        let to_amount = rlp::decode_u128(decoded.get_unchecked(9))?;
+        let data = decoded.get_unchecked(10);
 
-        Self {
+        Ok(Self {
             id,
             emitter,
             src_nid,
@@ -169,6 +170,6 @@ impl SwapOrder {
             to_token,
             to_amount,
             data,
-        }
+        })
     }
 }